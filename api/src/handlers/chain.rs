@@ -0,0 +1,22 @@
+use crate::primitives::{ErrorResponse, Setting};
+
+pub fn require_chain(setting: &Setting, chain_id: Option<&str>) -> Result<String, ErrorResponse> {
+    let chain_id = match chain_id {
+        Some(chain_id) if !chain_id.is_empty() => chain_id,
+        _ => {
+            return Err(ErrorResponse::InvalidRequest()
+                .with_error_description("Missing mandatory parameter `chain_id`")
+                .build()
+                .unwrap())
+        }
+    };
+
+    if setting.chain(chain_id).is_none() {
+        return Err(ErrorResponse::InvalidRequest()
+            .with_error_description(format!("Unsupported chain `{}`", chain_id))
+            .build()
+            .unwrap());
+    }
+
+    Ok(chain_id.to_owned())
+}
@@ -1 +1,7 @@
+mod chain;
+mod pagination;
+
 pub mod v1;
+
+pub use chain::require_chain;
+pub use pagination::paginate;
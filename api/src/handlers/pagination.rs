@@ -0,0 +1,67 @@
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait as _, QueryFilter as _,
+    QueryOrder as _, QuerySelect as _, Select,
+};
+
+use crate::{
+    entities::EntityId,
+    primitives::{PaginationCursor, PaginationRequest, PaginationResponseBuilder},
+};
+
+pub async fn paginate<E, T, D>(
+    db: &DatabaseConnection,
+    select: Select<E>,
+    id: E::Column,
+    form: &PaginationRequest<T>,
+    response: &mut PaginationResponseBuilder<T, D>,
+) -> Vec<E::Model>
+where
+    E: EntityTrait,
+    E::Model: EntityId + Sync,
+    T: Clone + Default,
+    D: Clone + Default,
+{
+    let select = select.order_by_desc(id);
+
+    response.with_size(form.size());
+
+    let models = match form.cursor() {
+        Some(cursor) => {
+            let mut models = select
+                .filter(id.lt(cursor.id()))
+                .limit(form.size() + 1)
+                .all(db)
+                .await
+                .unwrap_or_default();
+
+            if models.len() as u64 <= form.size() {
+                return models;
+            }
+
+            models.truncate(form.size() as usize);
+            models
+        }
+        None => {
+            response.with_page(form.page());
+
+            let select = select.paginate(db, form.size());
+
+            let total = select.num_pages().await.unwrap_or_default();
+            response.with_total(total);
+
+            let models = select.fetch_page(form.page()).await.unwrap_or_default();
+
+            if form.page() + 1 >= total {
+                return models;
+            }
+
+            models
+        }
+    };
+
+    if let Some(last) = models.last() {
+        response.with_next_cursor(PaginationCursor::new(last.id()));
+    }
+
+    models
+}
@@ -0,0 +1,134 @@
+use actix_web::{get, web, Error, HttpResponse};
+use sea_orm::{
+    prelude::TimeDateTimeWithTimeZone, ColumnTrait, DatabaseConnection, EntityTrait as _,
+    QueryFilter,
+};
+
+use crate::{
+    entities::extrinsic::{Column, Entity},
+    handlers::{paginate, require_chain},
+    primitives::{
+        v1::{ActivityFindRequest, ActivityResponse, AddressFindRequest, PortfolioResponse},
+        DataResponse, ErrorResponse, PaginationRequest, PaginationResponse, Setting,
+    },
+    services::{AssetManager, ExtrinsicManager},
+};
+
+#[get("/address/{address}")]
+pub async fn handle_address(
+    path: web::Path<(String,)>,
+    form: serde_qs::actix::QsQuery<AddressFindRequest>,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let setting = provider.get_required::<Setting>();
+    let asset_manager = provider.get_required::<AssetManager>();
+
+    let chain_id = match require_chain(&setting, form.chain_id.as_deref()) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
+    };
+
+    let response = match asset_manager.portfolio(&chain_id, &path.0).await {
+        Some(portfolio) => portfolio,
+        None => {
+            return Ok(HttpResponse::InternalServerError().json(ErrorResponse::EmptyImpossible()))
+        }
+    };
+
+    let response = DataResponse::<PortfolioResponse>::builder()
+        .with_data(response)
+        .build()
+        .unwrap();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[get("/address/{address}/activity")]
+pub async fn handle_address_activity(
+    path: web::Path<(String,)>,
+    form: serde_qs::actix::QsQuery<PaginationRequest<ActivityFindRequest>>,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let form = form.into_inner();
+
+    let mut response = PaginationResponse::<ActivityFindRequest, ActivityResponse>::builder();
+
+    let setting = provider.get_required::<Setting>();
+    let db = provider.get_required::<DatabaseConnection>();
+    let extrinsic_manager = provider.get_required::<ExtrinsicManager>();
+
+    let address = &path.0;
+
+    let select = Entity::find();
+
+    let chain_id = match require_chain(&setting, Some(&form.query().chain_id)) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
+    };
+
+    let select = select.filter(Column::ChainId.eq(&chain_id));
+
+    let select = select.filter(
+        Column::FromAddress
+            .eq(address)
+            .or(Column::ToAddress.eq(address)),
+    );
+
+    let select = match &form.query().asset_id {
+        Some(assets) => select.filter(Column::AssetId.is_in(assets)),
+        _ => select,
+    };
+
+    let select = match &form.query().operation {
+        Some(operations) => select.filter(Column::Operation.is_in(operations.clone())),
+        _ => select,
+    };
+
+    let since = form
+        .query()
+        .since
+        .map(TimeDateTimeWithTimeZone::from_unix_timestamp);
+
+    let select = match since {
+        Some(Ok(since)) => select.filter(Column::CreatedAt.gte(since)),
+        Some(Err(_)) => {
+            return Ok(HttpResponse::BadRequest().json(
+                ErrorResponse::InvalidRequest()
+                    .with_error_description("Malformed parameter `since`")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+        _ => select,
+    };
+
+    let until = form
+        .query()
+        .until
+        .map(TimeDateTimeWithTimeZone::from_unix_timestamp);
+
+    let select = match until {
+        Some(Ok(until)) => select.filter(Column::CreatedAt.lt(until)),
+        Some(Err(_)) => {
+            return Ok(HttpResponse::BadRequest().json(
+                ErrorResponse::InvalidRequest()
+                    .with_error_description("Malformed parameter `until`")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+        _ => select,
+    };
+
+    let extrinsics = paginate(db.as_ref(), select, Column::Id, &form, &mut response).await;
+
+    for extrinsic in extrinsics {
+        if let Some(activity) = extrinsic_manager.activity(&extrinsic, address).await {
+            response.append(activity);
+        }
+    }
+
+    let response = response.build().unwrap();
+
+    Ok(HttpResponse::Ok().json(response))
+}
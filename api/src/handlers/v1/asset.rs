@@ -1,14 +1,15 @@
 use actix_web::{get, web, Error, HttpResponse};
-use sea_orm::{
-    ColumnTrait, DatabaseConnection, EntityTrait as _, PaginatorTrait as _, QueryFilter,
-    QueryOrder as _,
-};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait as _, QueryFilter};
 
 use crate::{
-    entities::asset::{Column, Entity},
+    entities::{
+        self,
+        asset::{Column, Entity},
+    },
+    handlers::{paginate, require_chain},
     primitives::{
         v1::{AssetFindRequest, AssetResponse},
-        DataResponse, ErrorResponse, PaginationRequest, PaginationResponse,
+        DataResponse, ErrorResponse, PaginationRequest, PaginationResponse, Setting,
     },
     services::AssetManager,
 };
@@ -22,15 +23,62 @@ pub async fn handle_assets(
 
     let mut response = PaginationResponse::<AssetFindRequest, AssetResponse>::builder();
 
-    response.with_size(form.size()).with_page(form.page());
-
+    let setting = provider.get_required::<Setting>();
     let db = provider.get_required::<DatabaseConnection>();
     let asset_manager = provider.get_required::<AssetManager>();
 
+    if let Some(block) = form.query().at_block {
+        let chain_id = match require_chain(&setting, form.query().chain_id.as_deref()) {
+            Ok(chain_id) => chain_id,
+            Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
+        };
+
+        let address = match &form.query().address {
+            Some(address) => address,
+            None => {
+                return Ok(HttpResponse::BadRequest().json(
+                    ErrorResponse::InvalidRequest()
+                        .with_error_description("Missing mandatory parameter `address`")
+                        .build()
+                        .unwrap(),
+                ))
+            }
+        };
+
+        let select =
+            asset_manager.holdings(&chain_id, address, block, form.query().asset_id.as_deref());
+
+        let changes = paginate(
+            db.as_ref(),
+            select,
+            entities::asset_change::Column::Id,
+            &form,
+            &mut response,
+        )
+        .await;
+
+        for change in changes {
+            // locks are only tracked for the current head, skip them for past blocks
+            if let Some(asset) = asset_manager
+                .dump(&asset_manager.holding(change), false)
+                .await
+            {
+                response.append(asset);
+            }
+        }
+
+        let response = response.build().unwrap();
+
+        return Ok(HttpResponse::Ok().json(response));
+    }
+
     let select = Entity::find();
 
     let select = match &form.query().chain_id {
-        Some(chain_id) => select.filter(Column::ChainId.eq(chain_id)),
+        Some(chain_id) => match require_chain(&setting, Some(chain_id)) {
+            Ok(chain_id) => select.filter(Column::ChainId.eq(chain_id)),
+            Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
+        },
         _ => select,
     };
 
@@ -47,14 +95,7 @@ pub async fn handle_assets(
         ));
     };
 
-    let select = select
-        .order_by_desc(Column::Id)
-        .paginate(db.as_ref(), form.size());
-
-    let total = select.num_pages().await.unwrap_or_default();
-    response.with_total(total);
-
-    let assets = select.fetch_page(form.page()).await.unwrap_or_default();
+    let assets = paginate(db.as_ref(), select, Column::Id, &form, &mut response).await;
 
     for asset in assets {
         let asset = asset_manager.dump(&asset, true).await.unwrap();
@@ -73,18 +114,12 @@ pub async fn handle_asset(
     form: serde_qs::actix::QsQuery<AssetFindRequest>,
     provider: web::Data<di::ServiceProvider>,
 ) -> Result<HttpResponse, Error> {
+    let setting = provider.get_required::<Setting>();
     let asset_manager = provider.get_required::<AssetManager>();
 
-    let chain_id = match form.chain_id.clone() {
-        Some(chain_id) => chain_id,
-        _ => {
-            return Ok(HttpResponse::BadRequest().json(
-                ErrorResponse::InvalidRequest()
-                    .with_error_description("Missing mandatory parameter `chain_id`")
-                    .build()
-                    .unwrap(),
-            ))
-        }
+    let chain_id = match require_chain(&setting, form.chain_id.as_deref()) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
     };
 
     let address = match form.address.clone() {
@@ -132,18 +167,12 @@ pub async fn handle_nonfungible(
     form: serde_qs::actix::QsQuery<AssetFindRequest>,
     provider: web::Data<di::ServiceProvider>,
 ) -> Result<HttpResponse, Error> {
+    let setting = provider.get_required::<Setting>();
     let asset_manager = provider.get_required::<AssetManager>();
 
-    let chain_id = match form.chain_id.clone() {
-        Some(chain_id) => chain_id,
-        _ => {
-            return Ok(HttpResponse::BadRequest().json(
-                ErrorResponse::InvalidRequest()
-                    .with_error_description("Missing mandatory parameter `chain_id`")
-                    .build()
-                    .unwrap(),
-            ))
-        }
+    let chain_id = match require_chain(&setting, form.chain_id.as_deref()) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
     };
 
     let asset = match asset_manager.find_single(&chain_id, &path.0, &path.1).await {
@@ -0,0 +1,108 @@
+use actix_web::{get, web, Error, HttpResponse};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait as _, QueryFilter};
+
+use crate::{
+    entities::{
+        block::{Column, Entity},
+        NumberOrHash,
+    },
+    handlers::{paginate, require_chain},
+    primitives::{
+        v1::{BlockFindRequest, BlockResponse},
+        DataResponse, ErrorResponse, PaginationRequest, PaginationResponse, Setting,
+    },
+    services::BlockManager,
+};
+
+#[get("/block")]
+pub async fn handle_blocks(
+    form: serde_qs::actix::QsQuery<PaginationRequest<BlockFindRequest>>,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let form = form.into_inner();
+
+    let mut response = PaginationResponse::<BlockFindRequest, BlockResponse>::builder();
+
+    let setting = provider.get_required::<Setting>();
+    let db = provider.get_required::<DatabaseConnection>();
+    let block_manager = provider.get_required::<BlockManager>();
+
+    let select = Entity::find();
+
+    let chain_id = match require_chain(&setting, form.query().chain_id.as_deref()) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
+    };
+
+    let select = select.filter(Column::ChainId.eq(&chain_id));
+
+    let select = match &form.query().state {
+        Some(state) => select.filter(Column::State.is_in(state.clone())),
+        _ => select,
+    };
+
+    let blocks = paginate(db.as_ref(), select, Column::Id, &form, &mut response).await;
+
+    for block in blocks {
+        let block = block_manager.dump(&block, true).await.unwrap();
+
+        response.append(block);
+    }
+
+    let response = response.build().unwrap();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[get("/block/{block}")]
+pub async fn handle_block(
+    path: web::Path<(String,)>,
+    form: serde_qs::actix::QsQuery<BlockFindRequest>,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let setting = provider.get_required::<Setting>();
+    let block_manager = provider.get_required::<BlockManager>();
+
+    let chain_id = match require_chain(&setting, form.chain_id.as_deref()) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
+    };
+
+    let number_or_hash = match path.0.parse::<NumberOrHash>() {
+        Ok(number_or_hash) => number_or_hash,
+        Err(_) => {
+            return Ok(HttpResponse::BadRequest().json(
+                ErrorResponse::InvalidRequest()
+                    .with_error_description("Malformed block number or hash")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+    };
+
+    let block = match block_manager.find(&chain_id, number_or_hash).await {
+        Some(block) => block,
+        None => {
+            return Ok(HttpResponse::NotFound().json(
+                ErrorResponse::NotFound()
+                    .with_error_description("Block not found")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+    };
+
+    let response = match block_manager.dump(&block, true).await {
+        Some(block) => block,
+        None => {
+            return Ok(HttpResponse::InternalServerError().json(ErrorResponse::EmptyImpossible()))
+        }
+    };
+
+    let response = DataResponse::<BlockResponse>::builder()
+        .with_data(response)
+        .build()
+        .unwrap();
+
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -0,0 +1,34 @@
+use actix_web::{get, web, Error, HttpResponse};
+
+use crate::primitives::{v1::ChainResponse, DataResponse, Setting};
+
+#[get("/chain")]
+pub async fn handle_chains(
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let setting = provider.get_required::<Setting>();
+
+    let chains = setting
+        .chains()
+        .iter()
+        .filter_map(|(chain_id, chain)| {
+            ChainResponse::builder()
+                .with_chain_id(chain_id)
+                .with_name(chain.name())
+                .with_decimals(chain.decimals())
+                .with_fee(chain.fee().clone())
+                .with_fee_recipient(chain.fee_recipient())
+                .with_finality(chain.finality())
+                .with_protocols(chain.protocols().to_vec())
+                .build()
+                .ok()
+        })
+        .collect::<Vec<_>>();
+
+    let response = DataResponse::<Vec<ChainResponse>>::builder()
+        .with_data(chains)
+        .build()
+        .unwrap();
+
+    Ok(HttpResponse::Ok().json(response))
+}
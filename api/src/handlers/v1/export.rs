@@ -0,0 +1,268 @@
+use actix_web::{error, get, web, web::Bytes, Error, HttpResponse};
+use futures_util::StreamExt as _;
+use sea_orm::{ColumnTrait, DatabaseConnection, QueryFilter, QueryOrder, QuerySelect};
+use serde::Serialize;
+
+use crate::{
+    entities::extrinsic::Column,
+    handlers::require_chain,
+    primitives::{
+        v1::{
+            ExportFormat, ExportRequest, ExtrinsicExportResponse, ExtrinsicFindRequest,
+            HolderExportRequest, HolderExportResponse,
+        },
+        ErrorResponse, Setting,
+    },
+    services::{ContractManager, ExtrinsicManager},
+};
+
+// rows are read and flushed in chunks so exports are not bound by pagination
+const CHUNK: u64 = 500;
+
+// encode fails rather than leaving rows out, an export must not be silently incomplete
+fn encode<T: Serialize + Default>(
+    rows: &[T],
+    format: ExportFormat,
+    header: bool,
+) -> Result<Bytes, Error> {
+    match format {
+        ExportFormat::Csv => {
+            let mut writer = csv::WriterBuilder::new()
+                .has_headers(header)
+                .from_writer(Vec::new());
+
+            // csv headers come from the first row, an empty export takes them from a
+            // blank one so clients still get the columns
+            if header && rows.is_empty() {
+                writer
+                    .serialize(T::default())
+                    .map_err(error::ErrorInternalServerError)?;
+
+                let mut buffer = writer
+                    .into_inner()
+                    .map_err(|err| error::ErrorInternalServerError(err.to_string()))?;
+                let end = buffer
+                    .iter()
+                    .position(|byte| *byte == b'\n')
+                    .map_or(buffer.len(), |end| end + 1);
+                buffer.truncate(end);
+
+                return Ok(Bytes::from(buffer));
+            }
+
+            for row in rows {
+                writer
+                    .serialize(row)
+                    .map_err(error::ErrorInternalServerError)?;
+            }
+
+            let buffer = writer
+                .into_inner()
+                .map_err(|err| error::ErrorInternalServerError(err.to_string()))?;
+
+            Ok(Bytes::from(buffer))
+        }
+        ExportFormat::Ndjson => {
+            let mut buffer = Vec::new();
+
+            for row in rows {
+                let row = serde_json::to_vec(row).map_err(error::ErrorInternalServerError)?;
+                buffer.extend(row);
+                buffer.push(b'\n');
+            }
+
+            Ok(Bytes::from(buffer))
+        }
+    }
+}
+
+fn attachment(name: &str, format: ExportFormat) -> (&'static str, String) {
+    (
+        "Content-Disposition",
+        format!("attachment; filename=\"{}.{}\"", name, format.extension()),
+    )
+}
+
+#[get("/export/extrinsic")]
+pub async fn handle_extrinsic_export(
+    form: serde_qs::actix::QsQuery<ExportRequest<ExtrinsicFindRequest>>,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let form = form.into_inner();
+
+    let setting = provider.get_required::<Setting>();
+    let db = provider.get_required::<DatabaseConnection>();
+    let extrinsic_manager = provider.get_required::<ExtrinsicManager>();
+
+    let select = match super::extrinsic::select(&setting, form.query()) {
+        Ok(select) => select,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
+    };
+
+    let format = form.format();
+
+    // the cursor is the last exported id, None once the final chunk was sent
+    let stream = futures_util::stream::unfold(Some(i64::MIN), move |cursor| {
+        let select = select.clone();
+        let db = db.clone();
+        let extrinsic_manager = extrinsic_manager.clone();
+
+        async move {
+            let cursor = cursor?;
+
+            let extrinsics = match select
+                .filter(Column::Id.gt(cursor))
+                .order_by_asc(Column::Id)
+                .limit(CHUNK)
+                .all(db.as_ref())
+                .await
+            {
+                Ok(extrinsics) => extrinsics,
+                // abort the body so a truncated export is not mistaken for a full one
+                Err(err) => return Some((Err(error::ErrorInternalServerError(err)), None)),
+            };
+
+            let header = cursor == i64::MIN;
+
+            let last = match extrinsics.last() {
+                Some(extrinsic) => extrinsic.id,
+                None if header => {
+                    return Some((encode::<ExtrinsicExportResponse>(&[], format, header), None))
+                }
+                None => return None,
+            };
+
+            let mut rows = Vec::with_capacity(extrinsics.len());
+            for extrinsic in &extrinsics {
+                match extrinsic_manager.export(extrinsic).await {
+                    Some(row) => rows.push(row),
+                    None => {
+                        let err = format!("failed to export extrinsic {}", extrinsic.id);
+                        return Some((Err(error::ErrorInternalServerError(err)), None));
+                    }
+                }
+            }
+
+            let next = (extrinsics.len() as u64 == CHUNK).then_some(last);
+
+            Some((encode(&rows, format, header), next))
+        }
+    })
+    .boxed();
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header(attachment("extrinsic", format))
+        .streaming(stream))
+}
+
+#[get("/export/holder")]
+pub async fn handle_holder_export(
+    form: serde_qs::actix::QsQuery<ExportRequest<HolderExportRequest>>,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let form = form.into_inner();
+
+    let setting = provider.get_required::<Setting>();
+    let contract_manager = provider.get_required::<ContractManager>();
+
+    let chain_id = match require_chain(&setting, form.query().chain_id.as_deref()) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
+    };
+
+    if form.query().asset_id.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(
+            ErrorResponse::InvalidRequest()
+                .with_error_description("Missing mandatory parameter `asset_id`")
+                .build()
+                .unwrap(),
+        ));
+    }
+
+    let contract = match contract_manager
+        .find(&chain_id, &form.query().asset_id)
+        .await
+    {
+        Some(contract) => contract,
+        None => {
+            return Ok(HttpResponse::NotFound().json(
+                ErrorResponse::NotFound()
+                    .with_error_description("Contract not found")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+    };
+
+    let format = form.format();
+    let at_block = form.query().at_block;
+    let supply = contract_manager.total(&contract).await;
+
+    // the cursor is the offset of the next chunk, None once the final chunk was sent
+    let stream = futures_util::stream::unfold(Some(0), move |cursor| {
+        let contract = contract.clone();
+        let contract_manager = contract_manager.clone();
+
+        async move {
+            let cursor = cursor?;
+
+            let holders = match contract_manager
+                .holders(&contract, at_block, cursor, CHUNK)
+                .await
+            {
+                Ok(holders) => holders,
+                // abort the body so a truncated export is not mistaken for a full one
+                Err(err) => return Some((Err(error::ErrorInternalServerError(err)), None)),
+            };
+
+            let header = cursor == 0;
+
+            if holders.is_empty() {
+                return header.then(|| (encode::<HolderExportResponse>(&[], format, header), None));
+            }
+
+            let next = (holders.len() as u64 == CHUNK).then_some(cursor + CHUNK);
+
+            let rows = contract_manager.export(&contract, holders, supply);
+
+            Some((encode(&rows, format, header), next))
+        }
+    })
+    .boxed();
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header(attachment("holder", format))
+        .streaming(stream))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_the_header_of_empty_exports() {
+        let csv = encode::<HolderExportResponse>(&[], ExportFormat::Csv, true).unwrap();
+        assert_eq!(&csv[..], b"address,amount,share\n");
+
+        let ndjson = encode::<HolderExportResponse>(&[], ExportFormat::Ndjson, true).unwrap();
+        assert!(ndjson.is_empty());
+    }
+
+    #[test]
+    fn encodes_the_header_once() {
+        let rows = [HolderExportResponse::builder()
+            .with_address("0xaa")
+            .with_amount("1.5")
+            .with_share(0.5)
+            .build()
+            .unwrap()];
+
+        let first = encode(&rows, ExportFormat::Csv, true).unwrap();
+        assert_eq!(&first[..], b"address,amount,share\n0xaa,1.5,0.5\n");
+
+        let next = encode(&rows, ExportFormat::Csv, false).unwrap();
+        assert_eq!(&next[..], b"0xaa,1.5,0.5\n");
+    }
+}
@@ -1,17 +1,15 @@
 use actix_web::{get, web, Error, HttpResponse};
-use sea_orm::{
-    ColumnTrait, DatabaseConnection, EntityTrait as _, PaginatorTrait as _, QueryFilter,
-    QueryOrder as _,
-};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait as _, QueryFilter, Select};
 
 use crate::{
     entities::{
         extrinsic::{Column, Entity},
-        BlockState,
+        BlockState, NumberOrHash,
     },
+    handlers::{paginate, require_chain},
     primitives::{
         v1::{ExtrinsicFindRequest, ExtrinsicResponse},
-        DataResponse, ErrorResponse, PaginationRequest, PaginationResponse,
+        DataResponse, ErrorResponse, PaginationRequest, PaginationResponse, Setting,
     },
     services::ExtrinsicManager,
 };
@@ -25,28 +23,77 @@ pub async fn handle_extrinsics(
 
     let mut response = PaginationResponse::<ExtrinsicFindRequest, ExtrinsicResponse>::builder();
 
-    response.with_size(form.size()).with_page(form.page());
-
+    let setting = provider.get_required::<Setting>();
     let db = provider.get_required::<DatabaseConnection>();
     let extrinsic_manager = provider.get_required::<ExtrinsicManager>();
 
-    let select = Entity::find();
+    let select = match select(&setting, form.query()) {
+        Ok(select) => select,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
+    };
+
+    let extrinsics = paginate(db.as_ref(), select, Column::Id, &form, &mut response).await;
+
+    for extrinsic in extrinsics {
+        let extrinsic = extrinsic_manager.dump(&extrinsic, true).await.unwrap();
+
+        response.append(extrinsic);
+    }
+
+    let response = response.build().unwrap();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+// select applies the extrinsic filters shared by listings and exports
+pub(super) fn select(
+    setting: &Setting,
+    query: &ExtrinsicFindRequest,
+) -> Result<Select<Entity>, ErrorResponse> {
+    let chain_id = require_chain(setting, Some(&query.chain_id))?;
+
+    let select = Entity::find().filter(Column::ChainId.eq(&chain_id));
+
+    let block = match query.block.as_ref().map(|block| block.parse()) {
+        Some(Ok(block)) => Some(block),
+        Some(Err(_)) => {
+            return Err(ErrorResponse::InvalidRequest()
+                .with_error_description("Malformed parameter `block`")
+                .build()
+                .unwrap())
+        }
+        _ => None,
+    };
+
+    let select = match block {
+        Some(NumberOrHash::Number(block_number)) => {
+            select.filter(Column::BlockNumber.eq(block_number))
+        }
+        Some(NumberOrHash::Hash(block_hash)) => select.filter(Column::BlockHash.eq(block_hash)),
+        _ => select,
+    };
 
-    let select = select.filter(Column::ChainId.eq(&form.query().chain_id));
+    let select = match query.block_from {
+        Some(block_from) => select.filter(Column::BlockNumber.gte(block_from)),
+        _ => select,
+    };
 
-    // TODO: filter by block
+    let select = match query.block_to {
+        Some(block_to) => select.filter(Column::BlockNumber.lte(block_to)),
+        _ => select,
+    };
 
-    let select = match &form.query().tx_hash {
+    let select = match &query.tx_hash {
         Some(tx_hash) => select.filter(Column::TxHash.eq(tx_hash)),
         _ => select,
     };
 
-    let select = match &form.query().asset_id {
+    let select = match &query.asset_id {
         Some(assets) => select.filter(Column::AssetId.is_in(assets)),
         _ => select,
     };
 
-    let select = match &form.query().address {
+    let select = match &query.address {
         Some(addresses) => select.filter(
             Column::FromAddress
                 .is_in(addresses)
@@ -55,28 +102,54 @@ pub async fn handle_extrinsics(
         _ => select,
     };
 
-    let select = select.filter(
+    Ok(select.filter(
         Column::State
             .eq(BlockState::Finalized)
             .or(Column::State.eq(BlockState::Dropped)),
-    );
+    ))
+}
 
-    let select = select
-        .order_by_desc(Column::Id)
-        .paginate(db.as_ref(), form.size());
+#[get("/extrinsic/{hash}")]
+pub async fn handle_extrinsic_legs(
+    path: web::Path<(String,)>,
+    form: serde_qs::actix::QsQuery<ExtrinsicFindRequest>,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let setting = provider.get_required::<Setting>();
+    let extrinsic_manager = provider.get_required::<ExtrinsicManager>();
 
-    let total = select.num_pages().await.unwrap_or_default();
-    response.with_total(total);
+    let chain_id = match require_chain(&setting, Some(&form.chain_id)) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
+    };
 
-    let extrinsics = select.fetch_page(form.page()).await.unwrap_or_default();
+    let extrinsics = extrinsic_manager.legs(&chain_id, &path.0).await;
 
-    for extrinsic in extrinsics {
-        let extrinsic = extrinsic_manager.dump(&extrinsic, true).await.unwrap();
+    if extrinsics.is_empty() {
+        return Ok(HttpResponse::NotFound().json(
+            ErrorResponse::NotFound()
+                .with_error_description("Extrinsic not found")
+                .build()
+                .unwrap(),
+        ));
+    }
 
-        response.append(extrinsic);
+    let mut response = Vec::with_capacity(extrinsics.len());
+    for extrinsic in extrinsics {
+        match extrinsic_manager.dump(&extrinsic, true).await {
+            Some(extrinsic) => response.push(extrinsic),
+            None => {
+                return Ok(
+                    HttpResponse::InternalServerError().json(ErrorResponse::EmptyImpossible())
+                )
+            }
+        }
     }
 
-    let response = response.build().unwrap();
+    let response = DataResponse::<Vec<ExtrinsicResponse>>::builder()
+        .with_data(response)
+        .build()
+        .unwrap();
 
     Ok(HttpResponse::Ok().json(response))
 }
@@ -87,12 +160,15 @@ pub async fn handle_extrinsic(
     form: serde_qs::actix::QsQuery<ExtrinsicFindRequest>,
     provider: web::Data<di::ServiceProvider>,
 ) -> Result<HttpResponse, Error> {
+    let setting = provider.get_required::<Setting>();
     let extrinsic_manager = provider.get_required::<ExtrinsicManager>();
 
-    let extrinsic = match extrinsic_manager
-        .find(&form.chain_id, &path.0, path.1)
-        .await
-    {
+    let chain_id = match require_chain(&setting, Some(&form.chain_id)) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
+    };
+
+    let extrinsic = match extrinsic_manager.find(&chain_id, &path.0, path.1).await {
         Some(extrinsic) => extrinsic,
         None => {
             return Ok(HttpResponse::NotFound().json(
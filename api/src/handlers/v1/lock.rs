@@ -0,0 +1,83 @@
+use actix_web::{get, web, Error, HttpResponse};
+use sea_orm::{
+    prelude::TimeDateTimeWithTimeZone, sea_query::Condition, ColumnTrait, DatabaseConnection,
+    EntityTrait as _, QueryFilter,
+};
+
+use crate::{
+    entities::locked_asset::{Column, Entity},
+    handlers::{paginate, require_chain},
+    primitives::{
+        v1::{LockFindRequest, LockResponse},
+        ErrorResponse, PaginationRequest, PaginationResponse, Setting,
+    },
+    services::LockedAssetManager,
+};
+
+#[get("/lock")]
+pub async fn handle_locks(
+    form: serde_qs::actix::QsQuery<PaginationRequest<LockFindRequest>>,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let form = form.into_inner();
+
+    let mut response = PaginationResponse::<LockFindRequest, LockResponse>::builder();
+
+    let setting = provider.get_required::<Setting>();
+    let db = provider.get_required::<DatabaseConnection>();
+    let locked_asset_manager = provider.get_required::<LockedAssetManager>();
+
+    let chain_id = match require_chain(&setting, form.query().chain_id.as_deref()) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
+    };
+
+    let select = Entity::find().filter(Column::ChainId.eq(chain_id));
+
+    let select = match (&form.query().address, &form.query().delegate) {
+        (Some(address), Some(delegate)) => select
+            .filter(Column::Address.eq(address))
+            .filter(Column::Delegate.eq(delegate)),
+        (Some(address), None) => select.filter(Column::Address.eq(address)),
+        (None, Some(delegate)) => select.filter(Column::Delegate.eq(delegate)),
+        (None, None) => {
+            return Ok(HttpResponse::BadRequest().json(
+                ErrorResponse::InvalidRequest()
+                    .with_error_description("Missing mandatory parameter `address` or `delegate`")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+    };
+
+    let select = match &form.query().asset_id {
+        Some(assets) => select.filter(Column::AssetId.is_in(assets)),
+        None => select,
+    };
+
+    let now = TimeDateTimeWithTimeZone::now_utc().unix_timestamp();
+
+    let select = match form.query().active {
+        Some(true) => select
+            .filter(Column::ExpiresAt.gt(now))
+            .filter(Column::ReleasedBy.is_null()),
+        Some(false) => select.filter(
+            Condition::any()
+                .add(Column::ExpiresAt.lte(now))
+                .add(Column::ReleasedBy.is_not_null()),
+        ),
+        None => select,
+    };
+
+    let locks = paginate(db.as_ref(), select, Column::Id, &form, &mut response).await;
+
+    for lock in locks {
+        if let Some(lock) = locked_asset_manager.dump(&lock).await {
+            response.append(lock);
+        }
+    }
+
+    let response = response.build().unwrap();
+
+    Ok(HttpResponse::Ok().json(response))
+}
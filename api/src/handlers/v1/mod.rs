@@ -1,11 +1,34 @@
+mod address;
 mod asset;
+mod block;
+mod chain;
+mod export;
 mod extrinsic;
+mod lock;
+mod ordinal;
 mod status;
+mod stream;
+mod subscription;
 mod token;
 mod transaction;
+mod webhook;
 
+pub use address::{handle_address, handle_address_activity};
 pub use asset::{handle_asset, handle_assets, handle_nonfungible};
-pub use extrinsic::{handle_extrinsic, handle_extrinsics};
+pub use block::{handle_block, handle_blocks};
+pub use chain::handle_chains;
+pub use export::{handle_extrinsic_export, handle_holder_export};
+pub use extrinsic::{handle_extrinsic, handle_extrinsic_legs, handle_extrinsics};
+pub use lock::handle_locks;
+pub use ordinal::handle_ordinal_encode;
 pub use status::handle_status;
-pub use token::{handle_holder, handle_token, handle_token_deploy, handle_tokens};
+pub use stream::handle_extrinsic_stream;
+pub use subscription::handle_subscription;
+pub use token::{
+    handle_holder, handle_token, handle_token_deploy, handle_token_deploy_state, handle_tokens,
+};
 pub use transaction::{handle_transaction, handle_transactions};
+pub use webhook::{
+    handle_webhook, handle_webhook_create, handle_webhook_delete, handle_webhook_deliveries,
+    handle_webhook_update,
+};
@@ -0,0 +1,206 @@
+use actix_web::{post, web, Error, HttpResponse};
+
+use crate::{
+    entities::{ContractState, DropReason, ExtrinsicOperation},
+    handlers::require_chain,
+    primitives::{
+        ordinal::SingleOrBatch,
+        v1::{OrdinalEncodeRequest, OrdinalEncodeResponse},
+        DataResponse, ErrorResponse, Ordinal, Setting, Uint256,
+    },
+    services::ContractManager,
+};
+
+fn rejected(reason: DropReason) -> HttpResponse {
+    let reason = serde_json::to_value(reason)
+        .ok()
+        .and_then(|reason| reason.as_str().map(ToOwned::to_owned))
+        .unwrap_or_default();
+
+    HttpResponse::BadRequest().json(
+        ErrorResponse::InvalidRequest()
+            .with_error_description(format!("Inscription would be dropped with `{}`", reason))
+            .build()
+            .unwrap(),
+    )
+}
+
+#[post("/ordinal/encode")]
+pub async fn handle_ordinal_encode(
+    form: web::Either<
+        Result<web::Json<OrdinalEncodeRequest>, Error>,
+        Result<web::Form<OrdinalEncodeRequest>, Error>,
+    >,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let form = match form {
+        web::Either::Left(Ok(form)) => form.into_inner(),
+        web::Either::Right(Ok(form)) => form.into_inner(),
+        _ => {
+            return Ok(HttpResponse::BadRequest().json(
+                ErrorResponse::InvalidRequest()
+                    .with_error_description("Malformed request")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+    };
+
+    let setting = provider.get_required::<Setting>();
+    let contract_manager = provider.get_required::<ContractManager>();
+
+    let chain_id = match require_chain(&setting, form.chain_id.as_deref()) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
+    };
+
+    let protocol = match form.protocol {
+        Some(protocol) => protocol,
+        _ => {
+            return Ok(HttpResponse::BadRequest().json(
+                ErrorResponse::InvalidRequest()
+                    .with_error_description("Missing mandatory parameter `protocol`")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+    };
+
+    let operation = match form.operation {
+        Some(operation) => operation,
+        _ => {
+            return Ok(HttpResponse::BadRequest().json(
+                ErrorResponse::InvalidRequest()
+                    .with_error_description("Missing mandatory parameter `operation`")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+    };
+
+    let tick = match form.tick.clone() {
+        Some(tick) => tick,
+        _ => {
+            return Ok(HttpResponse::BadRequest().json(
+                ErrorResponse::InvalidRequest()
+                    .with_error_description("Missing mandatory parameter `tick`")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+    };
+
+    let chain = setting.chain(&chain_id).unwrap();
+
+    if !chain.protocols().contains(&protocol) {
+        return Ok(rejected(DropReason::ProtocolMismatch));
+    }
+
+    let mut ordinal = Ordinal::builder();
+    ordinal
+        .with_protocol(protocol)
+        .with_tick(&tick)
+        .with_operation(operation);
+
+    match form.batch {
+        Some(batch) => ordinal.with_operand(SingleOrBatch::Batch(
+            batch.into_iter().map(Into::into).collect(),
+        )),
+        None => ordinal.with_operand(SingleOrBatch::Single(form.operand.into())),
+    };
+
+    if let Some(name) = form.name {
+        ordinal.with_name(name);
+    }
+
+    if let Some(max_supply) = form.max_supply {
+        ordinal.with_max_supply(max_supply);
+    }
+
+    if let Some(mint_limit) = form.mint_limit {
+        ordinal.with_mint_limit(mint_limit);
+    }
+
+    if let Some(uri) = form.uri {
+        ordinal.with_uri(uri);
+    }
+
+    if let Some(not_before) = form.not_before {
+        ordinal.with_not_before(not_before);
+    }
+
+    if let Some(period) = form.period {
+        ordinal.with_period(period);
+    }
+
+    if let Some(expires_at) = form.expires_at {
+        ordinal.with_expires_at(expires_at);
+    }
+
+    let ordinal = ordinal.build().unwrap();
+
+    if let Err(reason) = ordinal.validate() {
+        return Ok(rejected(reason));
+    }
+
+    // decoding the output again guarantees the calldata is what the indexer will read
+    let calldata = ordinal.calldata();
+    if let Err(reason) = Ordinal::parse(&calldata).and_then(|ordinal| ordinal.validate()) {
+        return Ok(rejected(reason));
+    }
+
+    let mut response = OrdinalEncodeResponse::builder();
+    response
+        .with_chain_id(&chain_id)
+        .with_inscription(ordinal.encode())
+        .with_calldata(calldata);
+
+    match operation {
+        ExtrinsicOperation::Deploy => {
+            if let Some(contract) = contract_manager.find(&chain_id, &tick).await {
+                if !contract_manager.expired(&contract) {
+                    return Ok(HttpResponse::Conflict().json(
+                        ErrorResponse::Conflict()
+                            .with_error_description("Token name already taken")
+                            .build()
+                            .unwrap(),
+                    ));
+                }
+            }
+
+            response
+                .with_to_address(chain.fee_recipient())
+                .with_fee(chain.fee().clone());
+        }
+        _ => {
+            let contract = match contract_manager.find(&chain_id, &tick).await {
+                Some(contract) if contract.state == ContractState::Deployed => contract,
+                _ => {
+                    return Ok(HttpResponse::NotFound().json(
+                        ErrorResponse::NotFound()
+                            .with_error_description("Token not found")
+                            .build()
+                            .unwrap(),
+                    ))
+                }
+            };
+
+            if contract.protocol != protocol {
+                return Ok(rejected(DropReason::ProtocolMismatch));
+            }
+
+            response
+                .with_to_address(&contract.address)
+                .with_fee(Uint256::default());
+        }
+    }
+
+    let response = response.build().unwrap();
+
+    let response = DataResponse::<OrdinalEncodeResponse>::builder()
+        .with_data(response)
+        .build()
+        .unwrap();
+
+    Ok(HttpResponse::Ok().json(response))
+}
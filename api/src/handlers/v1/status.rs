@@ -1,17 +1,23 @@
+use std::collections::HashMap;
+
 use actix_web::{get, web, Error, HttpResponse};
 use sea_orm::{
-    ColumnTrait, DatabaseConnection, EntityTrait as _, PaginatorTrait as _, QueryFilter,
+    ColumnTrait, DatabaseConnection, EntityTrait as _, Order, PaginatorTrait as _, QueryFilter,
+    QuerySelect as _, Select,
 };
 
 use crate::{
     entities::{
+        self,
         extrinsic::{Column, Entity},
-        BlockState,
+        BlockState, DropReason,
     },
+    handlers::require_chain,
     primitives::{
-        v1::{StatusRequest, StatusResponse},
-        ErrorResponse,
+        v1::{StatusRequest, StatusResponse, StatusResponseBuilder},
+        Setting,
     },
+    services::BlockManager,
 };
 
 #[get("/status")]
@@ -19,27 +25,81 @@ pub async fn handle_status(
     form: serde_qs::actix::QsQuery<StatusRequest>,
     provider: web::Data<di::ServiceProvider>,
 ) -> Result<HttpResponse, Error> {
+    let setting = provider.get_required::<Setting>();
     let db = provider.get_required::<DatabaseConnection>();
+    let block_manager = provider.get_required::<BlockManager>();
 
     let select = Entity::find();
 
-    let select = match &form.chain_id {
-        Some(chain_id) => select.filter(Column::ChainId.eq(chain_id)),
-        _ => {
-            return Ok(HttpResponse::BadRequest().json(
-                ErrorResponse::InvalidRequest()
-                    .with_error_description("Missing mandatory parameter `chain_id`")
-                    .build()
-                    .unwrap(),
-            ))
-        }
+    let chain_id = match require_chain(&setting, form.chain_id.as_deref()) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
     };
 
-    let select = match &form.asset_id {
-        Some(assets) => select.filter(Column::AssetId.is_in(assets)),
-        _ => select,
+    let select = select.filter(Column::ChainId.eq(&chain_id));
+
+    let mut response = match &form.asset_id {
+        Some(assets) => {
+            let mut response =
+                statistic(&db, select.clone().filter(Column::AssetId.is_in(assets))).await;
+
+            if assets.len() > 1 {
+                let mut breakdown = HashMap::new();
+                for asset in assets {
+                    let statistic =
+                        statistic(&db, select.clone().filter(Column::AssetId.eq(asset))).await;
+
+                    breakdown.insert(asset.clone(), statistic.build().unwrap());
+                }
+
+                response.with_assets(breakdown);
+            }
+
+            response
+        }
+        _ => statistic(&db, select).await,
     };
 
+    let latest = block_manager
+        .query(
+            vec![entities::block::Column::ChainId.eq(&chain_id)],
+            vec![(entities::block::Column::BlockNumber, Order::Desc)],
+            Some(1),
+        )
+        .await;
+
+    let finalized = block_manager
+        .query(
+            vec![
+                entities::block::Column::ChainId.eq(&chain_id),
+                entities::block::Column::State.eq(BlockState::Finalized),
+            ],
+            vec![(entities::block::Column::BlockNumber, Order::Desc)],
+            Some(1),
+        )
+        .await;
+
+    if let Some(latest) = latest.first() {
+        response.with_latest_block(latest.block_number);
+
+        if let Some(indexed_at) = latest.created_at {
+            response.with_latest_block_at(indexed_at);
+        }
+
+        // without a finalized block there is nothing to measure the lag against
+        if let Some(finalized) = finalized.first() {
+            response
+                .with_finalized_block(finalized.block_number)
+                .with_lag(latest.block_number - finalized.block_number);
+        }
+    }
+
+    let response = response.build().unwrap();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+async fn statistic(db: &DatabaseConnection, select: Select<Entity>) -> StatusResponseBuilder {
     let pending = select
         .clone()
         .filter(
@@ -47,7 +107,7 @@ pub async fn handle_status(
                 .eq(BlockState::Indexing)
                 .or(Column::State.eq(BlockState::Confirmed))),
         )
-        .count(db.as_ref())
+        .count(db)
         .await
         .ok()
         .unwrap_or(0);
@@ -55,7 +115,7 @@ pub async fn handle_status(
     let finalized = select
         .clone()
         .filter(Column::State.eq(BlockState::Finalized))
-        .count(db.as_ref())
+        .count(db)
         .await
         .ok()
         .unwrap_or(0);
@@ -63,18 +123,35 @@ pub async fn handle_status(
     let dropped = select
         .clone()
         .filter(Column::State.eq(BlockState::Dropped))
-        .count(db.as_ref())
+        .count(db)
         .await
         .ok()
         .unwrap_or(0);
 
+    let drop_reasons = select
+        .select_only()
+        .column(Column::DropReason)
+        .column_as(Column::Id.count(), "count")
+        .filter(Column::State.eq(BlockState::Dropped))
+        .group_by(Column::DropReason)
+        .into_tuple::<(Option<DropReason>, i64)>()
+        .all(db)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .fold(HashMap::new(), |mut drop_reasons, (drop_reason, count)| {
+            *drop_reasons
+                .entry(drop_reason.unwrap_or_default())
+                .or_default() += count as u64;
+            drop_reasons
+        });
+
     let mut response = StatusResponse::builder();
     response
         .with_pending(pending)
         .with_finalized(finalized)
-        .with_dropped(dropped);
+        .with_dropped(dropped)
+        .with_drop_reasons(drop_reasons);
 
-    let response = response.build().unwrap();
-
-    Ok(HttpResponse::Ok().json(response))
+    response
 }
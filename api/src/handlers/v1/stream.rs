@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use actix_web::{get, web, web::Bytes, Error, HttpResponse};
+use futures_util::StreamExt as _;
+use tokio_stream::wrappers::{BroadcastStream, IntervalStream};
+
+use crate::{
+    handlers::require_chain,
+    primitives::{v1::ExtrinsicStreamRequest, Setting},
+    services::{Event, EventService, ExtrinsicManager},
+};
+
+#[get("/stream/extrinsic")]
+pub async fn handle_extrinsic_stream(
+    form: serde_qs::actix::QsQuery<ExtrinsicStreamRequest>,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let form = form.into_inner();
+
+    let setting = provider.get_required::<Setting>();
+    let event = provider.get_required::<EventService>();
+    let extrinsic_manager = provider.get_required::<ExtrinsicManager>();
+
+    if let Err(error) = require_chain(&setting, Some(&form.chain_id)) {
+        return Ok(HttpResponse::BadRequest().json(error));
+    }
+
+    let events = BroadcastStream::new(event.subscribe())
+        .filter_map(move |event| {
+            let form = form.clone();
+            let extrinsic_manager = extrinsic_manager.clone();
+
+            async move {
+                let extrinsic = match event {
+                    Ok(Event::Extrinsic(extrinsic)) if form.matches(&extrinsic) => extrinsic,
+                    _ => return None,
+                };
+
+                let response = extrinsic_manager.dump(&extrinsic, false).await?;
+                let response = serde_json::to_string(&response).ok()?;
+
+                Some(Bytes::from(format!(
+                    "event: extrinsic\ndata: {}\n\n",
+                    response
+                )))
+            }
+        })
+        .boxed();
+
+    let keep_alive = Duration::from_secs(setting.stream().keep_alive());
+    let keep_alive = IntervalStream::new(actix_web::rt::time::interval(keep_alive))
+        .map(|_| Bytes::from_static(b": keep-alive\n\n"));
+
+    let stream = futures_util::stream::select(events, keep_alive).map(Ok::<_, Error>);
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(stream))
+}
@@ -0,0 +1,226 @@
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use actix_web::{get, rt, web, Error, HttpRequest, HttpResponse};
+use actix_ws::{CloseCode, CloseReason, Message, MessageStream, Session};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{
+    entities::{BlockState, ExtrinsicOperation},
+    primitives::{
+        v1::{ReorgResponse, SubscriptionMessage, SubscriptionRequest, Topic},
+        Setting,
+    },
+    services::{BlockManager, Event, EventService, ExtrinsicManager},
+};
+
+const MAX_TOPICS: usize = 64;
+
+#[get("/ws")]
+pub async fn handle_subscription(
+    req: HttpRequest,
+    body: web::Payload,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let (response, session, stream) = actix_ws::handle(&req, body)?;
+
+    let setting = provider.get_required::<Setting>();
+
+    let subscriber = Subscriber {
+        session,
+        setting: setting.clone(),
+        topics: HashSet::new(),
+        timeout: Duration::from_secs(setting.stream().keep_alive()),
+        block_manager: provider.get_required::<BlockManager>(),
+        extrinsic_manager: provider.get_required::<ExtrinsicManager>(),
+    };
+
+    rt::spawn(subscriber.run(stream, provider.get_required::<EventService>()));
+
+    Ok(response)
+}
+
+struct Subscriber {
+    session: Session,
+    setting: Arc<Setting>,
+    topics: HashSet<Topic>,
+    timeout: Duration,
+    block_manager: Arc<BlockManager>,
+    extrinsic_manager: Arc<ExtrinsicManager>,
+}
+
+impl Subscriber {
+    async fn run(mut self, mut stream: MessageStream, event: Arc<EventService>) {
+        let mut events = event.subscribe();
+        let mut heartbeat = rt::time::interval(self.timeout);
+        let mut last_seen = Instant::now();
+
+        let reason = loop {
+            tokio::select! {
+                message = stream.recv() => {
+                    last_seen = Instant::now();
+
+                    let result = match message {
+                        Some(Ok(Message::Text(text))) => self.request(&text).await,
+                        Some(Ok(Message::Ping(bytes))) => {
+                            self.session.pong(&bytes).await.map_err(|_| None)
+                        }
+                        Some(Ok(Message::Close(reason))) => break reason,
+                        Some(Ok(_)) => Ok(()),
+                        Some(Err(_)) | None => break None,
+                    };
+
+                    if let Err(reason) = result {
+                        break reason;
+                    }
+                }
+                event = events.recv() => {
+                    let result = match event {
+                        Ok(event) => self.publish(event).await,
+                        Err(RecvError::Lagged(_)) => Err(Some(slow_consumer())),
+                        Err(RecvError::Closed) => Err(None),
+                    };
+
+                    if let Err(reason) = result {
+                        break reason;
+                    }
+                }
+                _ = heartbeat.tick() => {
+                    if last_seen.elapsed() > self.timeout * 2 {
+                        break Some(CloseReason {
+                            code: CloseCode::Away,
+                            description: Some("heartbeat timeout".to_owned()),
+                        });
+                    }
+
+                    if self.session.ping(b"").await.is_err() {
+                        break None;
+                    }
+                }
+            }
+        };
+
+        let _ = self.session.close(reason).await;
+    }
+
+    async fn request(&mut self, text: &str) -> Result<(), Option<CloseReason>> {
+        let message = match serde_json::from_str::<SubscriptionRequest>(text) {
+            Ok(SubscriptionRequest::Subscribe { topic }) => {
+                if self.setting.chain(topic.chain_id()).is_none() {
+                    SubscriptionMessage::Error {
+                        error_description: format!("Unsupported chain `{}`", topic.chain_id()),
+                    }
+                } else if self.topics.len() >= MAX_TOPICS && !self.topics.contains(&topic) {
+                    SubscriptionMessage::Error {
+                        error_description: "Too many subscriptions".to_owned(),
+                    }
+                } else {
+                    self.topics.insert(topic.clone());
+                    SubscriptionMessage::Subscribed { topic }
+                }
+            }
+            Ok(SubscriptionRequest::Unsubscribe { topic }) => {
+                self.topics.remove(&topic);
+                SubscriptionMessage::Unsubscribed { topic }
+            }
+            Err(_) => SubscriptionMessage::Error {
+                error_description: "Malformed subscription request".to_owned(),
+            },
+        };
+
+        self.send(&message).await
+    }
+
+    async fn publish(&mut self, event: Event) -> Result<(), Option<CloseReason>> {
+        let mut messages = Vec::new();
+
+        for topic in &self.topics {
+            let message = match (topic, &event) {
+                (Topic::Block { chain_id }, Event::Block(block)) if &block.chain_id == chain_id => {
+                    self.block_manager.dump(block, false).await.map(|data| {
+                        SubscriptionMessage::Block {
+                            topic: topic.clone(),
+                            data,
+                        }
+                    })
+                }
+                (Topic::Block { chain_id }, Event::Reorg(block, extrinsics))
+                    if &block.chain_id == chain_id =>
+                {
+                    let mut data = ReorgResponse::builder();
+                    data.with_chain_id(&block.chain_id)
+                        .with_number(block.block_number)
+                        .with_hash(&block.block_hash);
+
+                    for extrinsic in extrinsics {
+                        if let Some(extrinsic) = self.extrinsic_manager.dump(extrinsic, false).await
+                        {
+                            data.append(extrinsic);
+                        }
+                    }
+
+                    data.build().ok().map(|data| SubscriptionMessage::Reorg {
+                        topic: topic.clone(),
+                        data,
+                    })
+                }
+                (Topic::Mint { chain_id, asset_id }, Event::Extrinsic(extrinsic))
+                    if &extrinsic.chain_id == chain_id
+                        && &extrinsic.asset_id == asset_id
+                        && extrinsic.operation == ExtrinsicOperation::Mint =>
+                {
+                    self.extrinsic_manager
+                        .dump(extrinsic, false)
+                        .await
+                        .map(|data| SubscriptionMessage::Mint {
+                            topic: topic.clone(),
+                            data,
+                        })
+                }
+                (Topic::Balance { chain_id, address }, Event::Extrinsic(extrinsic))
+                    if &extrinsic.chain_id == chain_id
+                        && extrinsic.state == BlockState::Finalized
+                        && (&extrinsic.from_address == address
+                            || &extrinsic.to_address == address) =>
+                {
+                    self.extrinsic_manager
+                        .activity(extrinsic, address)
+                        .await
+                        .map(|data| SubscriptionMessage::Balance {
+                            topic: topic.clone(),
+                            data,
+                        })
+                }
+                _ => None,
+            };
+
+            messages.extend(message);
+        }
+
+        for message in messages {
+            self.send(&message).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn send(&mut self, message: &SubscriptionMessage) -> Result<(), Option<CloseReason>> {
+        let text = serde_json::to_string(message).map_err(|_| None)?;
+
+        match rt::time::timeout(self.timeout, self.session.text(text)).await {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(_)) => Err(None),
+            Err(_) => Err(Some(slow_consumer())),
+        }
+    }
+}
+
+fn slow_consumer() -> CloseReason {
+    CloseReason {
+        code: CloseCode::Policy,
+        description: Some("slow consumer".to_owned()),
+    }
+}
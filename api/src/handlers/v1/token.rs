@@ -1,16 +1,17 @@
+use std::time::Duration;
+
 use actix_web::{get, post, web, Error, HttpResponse};
-use sea_orm::{
-    ColumnTrait, DatabaseConnection, EntityTrait as _, PaginatorTrait as _, QueryFilter,
-    QueryOrder as _,
-};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait as _, QueryFilter, SqlErr};
 
 use crate::{
     entities::contract::{Column, Entity},
+    handlers::{paginate, require_chain},
     primitives::{
-        v1::{ContractDeployRequest, ContractFindRequest, ContractResponse},
-        DataResponse, ErrorResponse, PaginationRequest, PaginationResponse, Uint256,
+        ordinal::VerifyingKey,
+        v1::{ContractDeployRequest, ContractFindRequest, ContractResponse, HolderResponse},
+        DataResponse, ErrorResponse, PaginationRequest, PaginationResponse, Setting,
     },
-    services::ContractManager,
+    services::{validate_key, ContractManager},
 };
 
 #[get("/token")]
@@ -22,44 +23,43 @@ pub async fn handle_tokens(
 
     let mut response = PaginationResponse::<ContractFindRequest, ContractResponse>::builder();
 
-    response.with_size(form.size()).with_page(form.page());
-
+    let setting = provider.get_required::<Setting>();
     let db = provider.get_required::<DatabaseConnection>();
     let contract_manager = provider.get_required::<ContractManager>();
 
     let select = Entity::find();
 
-    let select = match &form.query().chain_id {
-        Some(chain_id) => select.filter(Column::ChainId.eq(chain_id)),
-        _ => {
-            return Ok(HttpResponse::BadRequest().json(
-                ErrorResponse::InvalidRequest()
-                    .with_error_description("Missing mandatory parameter `chain_id`")
-                    .build()
-                    .unwrap(),
-            ))
-        }
+    let chain_id = match require_chain(&setting, form.query().chain_id.as_deref()) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
     };
 
-    // TODO: filter by type
-    // let select = match form.query().r#type {
-    //     Some(r#type) => select.filter(Column::Type.is_in(r#type)),
-    //     _ => select,
-    // };
+    let select = select.filter(Column::ChainId.eq(&chain_id));
+
+    let protocols = form.query().r#type.as_ref().map(|types| {
+        types
+            .iter()
+            .flat_map(|r#type| r#type.protocols())
+            .collect::<Vec<_>>()
+    });
+
+    let protocols = match (protocols, form.query().protocol.clone()) {
+        (Some(protocols), Some(protocol)) => Some(
+            protocols
+                .into_iter()
+                .filter(|p| protocol.contains(p))
+                .collect(),
+        ),
+        (Some(protocols), None) => Some(protocols),
+        (None, protocol) => protocol,
+    };
 
-    let select = match form.query().protocol.clone() {
-        Some(protocol) => select.filter(Column::Protocol.is_in(protocol)),
+    let select = match protocols {
+        Some(protocols) => select.filter(Column::Protocol.is_in(protocols)),
         _ => select,
     };
 
-    let select = select
-        .order_by_desc(Column::Id)
-        .paginate(db.as_ref(), form.size());
-
-    let total = select.num_pages().await.unwrap_or_default();
-    response.with_total(total);
-
-    let contracts = select.fetch_page(form.page()).await.unwrap_or_default();
+    let contracts = paginate(db.as_ref(), select, Column::Id, &form, &mut response).await;
 
     for contract in contracts {
         let contract = contract_manager.dump(&contract, true).await.unwrap();
@@ -78,18 +78,12 @@ pub async fn handle_token(
     form: serde_qs::actix::QsQuery<ContractFindRequest>,
     provider: web::Data<di::ServiceProvider>,
 ) -> Result<HttpResponse, Error> {
+    let setting = provider.get_required::<Setting>();
     let contract_manager = provider.get_required::<ContractManager>();
 
-    let chain_id = match form.chain_id.clone() {
-        Some(chain_id) => chain_id,
-        _ => {
-            return Ok(HttpResponse::BadRequest().json(
-                ErrorResponse::InvalidRequest()
-                    .with_error_description("Missing mandatory parameter `chain_id`")
-                    .build()
-                    .unwrap(),
-            ))
-        }
+    let chain_id = match require_chain(&setting, form.chain_id.as_deref()) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
     };
 
     let contract = match contract_manager.find(&chain_id, &path.0).await {
@@ -122,21 +116,21 @@ pub async fn handle_token(
 #[get("/token/{contract}/holder")]
 pub async fn handle_holder(
     path: web::Path<(String,)>,
-    form: serde_qs::actix::QsQuery<ContractFindRequest>,
+    form: serde_qs::actix::QsQuery<PaginationRequest<ContractFindRequest>>,
     provider: web::Data<di::ServiceProvider>,
 ) -> Result<HttpResponse, Error> {
+    let form = form.into_inner();
+
+    let mut response = PaginationResponse::<ContractFindRequest, HolderResponse>::builder();
+
+    response.with_size(form.size()).with_page(form.page());
+
+    let setting = provider.get_required::<Setting>();
     let contract_manager = provider.get_required::<ContractManager>();
 
-    let chain_id = match form.chain_id.clone() {
-        Some(chain_id) => chain_id,
-        _ => {
-            return Ok(HttpResponse::BadRequest().json(
-                ErrorResponse::InvalidRequest()
-                    .with_error_description("Missing mandatory parameter `chain_id`")
-                    .build()
-                    .unwrap(),
-            ))
-        }
+    let chain_id = match require_chain(&setting, form.query().chain_id.as_deref()) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
     };
 
     let contract = match contract_manager.find(&chain_id, &path.0).await {
@@ -151,12 +145,20 @@ pub async fn handle_holder(
         }
     };
 
-    let holders = contract_manager.holder(&contract, 20).await;
+    let at_block = form.query().at_block;
 
-    let response = DataResponse::<Vec<(String, i64)>>::builder()
-        .with_data(holders)
-        .build()
-        .unwrap();
+    let total = contract_manager.holder_count(&contract, at_block).await;
+    response.with_total(total.div_ceil(form.size().max(1)));
+
+    let holders = contract_manager
+        .holder(&contract, at_block, form.page(), form.size())
+        .await;
+
+    for holder in holders {
+        response.append(holder);
+    }
+
+    let response = response.build().unwrap();
 
     Ok(HttpResponse::Ok().json(response))
 }
@@ -182,56 +184,109 @@ pub async fn handle_token_deploy(
         }
     };
 
+    let setting = provider.get_required::<Setting>();
     let contract_manager = provider.get_required::<ContractManager>();
 
-    let chain_id = match form.chain_id.clone() {
-        Some(chain_id) => chain_id,
+    let chain_id = match require_chain(&setting, form.chain_id.as_deref()) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
+    };
+
+    let name = match form.name.clone() {
+        Some(name) => name,
         _ => {
             return Ok(HttpResponse::BadRequest().json(
                 ErrorResponse::InvalidRequest()
-                    .with_error_description("Missing mandatory parameter `chain_id`")
+                    .with_error_description("Missing mandatory parameter `name`")
                     .build()
                     .unwrap(),
             ))
         }
     };
 
-    let name = match form.name.clone() {
-        Some(name) => name,
+    let protocol = match form.protocol {
+        Some(protocol) => protocol,
         _ => {
             return Ok(HttpResponse::BadRequest().json(
                 ErrorResponse::InvalidRequest()
-                    .with_error_description("Missing mandatory parameter `name`")
+                    .with_error_description("Missing mandatory parameter `protocol`")
                     .build()
                     .unwrap(),
             ))
         }
     };
 
-    if contract_manager.find(&chain_id, &name).await.is_some() {
+    let chain = setting.chain(&chain_id).unwrap();
+
+    if !chain.protocols().contains(&protocol) {
         return Ok(HttpResponse::BadRequest().json(
-            ErrorResponse::Conflict()
-                .with_error_description("Token name already taken")
+            ErrorResponse::InvalidRequest()
+                .with_error_description("Unsupported protocol on this chain")
                 .build()
                 .unwrap(),
         ));
     }
 
-    let mut response = ContractResponse::builder();
-    response
-        .with_chain_id(&chain_id)
-        .with_name(&name)
-        .with_symbol(&name);
+    if let Some(verifying_key) = &form.verifying_key {
+        match serde_json::from_str::<VerifyingKey>(verifying_key) {
+            Ok(verifying_key) if validate_key(&verifying_key) => (),
+            _ => {
+                return Ok(HttpResponse::BadRequest().json(
+                    ErrorResponse::InvalidRequest()
+                        .with_error_description("Malformed verifying key")
+                        .build()
+                        .unwrap(),
+                ))
+            }
+        }
+    }
 
-    if let Some(protocol) = form.protocol {
-        response.with_type(protocol).with_protocol(protocol);
+    if let Some(contract) = contract_manager.find(&chain_id, &name).await {
+        if !contract_manager.expired(&contract) {
+            return Ok(HttpResponse::Conflict().json(
+                ErrorResponse::Conflict()
+                    .with_error_description("Token name already taken")
+                    .build()
+                    .unwrap(),
+            ));
+        }
     }
 
-    response
-        .with_to_address("AeDB27Cc7AEe4Dc74c02CfCc80F71ffF7a3Dfe36")
-        .with_fee(Uint256::from_str_prefixed("0x1A055690D9DB80000").unwrap());
+    let contract = match contract_manager
+        .reserve(
+            &chain_id,
+            &name,
+            protocol,
+            form.address.as_deref(),
+            chain.fee_recipient(),
+            Duration::from_secs(setting.deploy().expiry()),
+            form.verifying_key.as_deref(),
+        )
+        .await
+    {
+        Ok(contract) => contract,
+        // the unique index rejects a reservation that raced this one
+        Err(err) if matches!(err.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+            return Ok(HttpResponse::Conflict().json(
+                ErrorResponse::Conflict()
+                    .with_error_description("Token name already taken")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+        Err(err) => {
+            log::error!("failed to reserve token name: {}", err);
 
-    let response = response.build().unwrap();
+            return Ok(HttpResponse::InternalServerError().json(ErrorResponse::EmptyImpossible()));
+        }
+    };
+
+    let response = match contract_manager.dump(&contract, false).await {
+        Some(contract) => contract,
+        None => {
+            return Ok(HttpResponse::InternalServerError().json(ErrorResponse::EmptyImpossible()))
+        }
+    };
 
     let response = DataResponse::<ContractResponse>::builder()
         .with_data(response)
@@ -240,3 +295,37 @@ pub async fn handle_token_deploy(
 
     Ok(HttpResponse::Accepted().json(response))
 }
+
+#[get("/deploy/{id}")]
+pub async fn handle_token_deploy_state(
+    path: web::Path<(i64,)>,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let contract_manager = provider.get_required::<ContractManager>();
+
+    let contract = match contract_manager.deploy(path.0).await {
+        Some(contract) => contract,
+        None => {
+            return Ok(HttpResponse::NotFound().json(
+                ErrorResponse::NotFound()
+                    .with_error_description("Deploy not found")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+    };
+
+    let response = match contract_manager.dump(&contract, false).await {
+        Some(contract) => contract,
+        None => {
+            return Ok(HttpResponse::InternalServerError().json(ErrorResponse::EmptyImpossible()))
+        }
+    };
+
+    let response = DataResponse::<ContractResponse>::builder()
+        .with_data(response)
+        .build()
+        .unwrap();
+
+    Ok(HttpResponse::Ok().json(response))
+}
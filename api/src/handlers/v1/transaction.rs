@@ -1,14 +1,15 @@
 use actix_web::{get, web, Error, HttpResponse};
-use sea_orm::{
-    ColumnTrait, DatabaseConnection, EntityTrait as _, PaginatorTrait as _, QueryFilter,
-    QueryOrder as _,
-};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait as _, QueryFilter};
 
 use crate::{
-    entities::transaction::{Column, Entity},
+    entities::{
+        transaction::{Column, Entity},
+        NumberOrHash,
+    },
+    handlers::{paginate, require_chain},
     primitives::{
-        v1::{TransactionFindRequest, TransactionResponse},
-        DataResponse, ErrorResponse, PaginationRequest, PaginationResponse,
+        v1::{TransactionDirection, TransactionFindRequest, TransactionResponse},
+        DataResponse, ErrorResponse, PaginationRequest, PaginationResponse, Setting,
     },
     services::TransactionManager,
 };
@@ -22,30 +23,54 @@ pub async fn handle_transactions(
 
     let mut response = PaginationResponse::<TransactionFindRequest, TransactionResponse>::builder();
 
-    response.with_size(form.size()).with_page(form.page());
-
+    let setting = provider.get_required::<Setting>();
     let db = provider.get_required::<DatabaseConnection>();
     let transaction_manager = provider.get_required::<TransactionManager>();
 
     let select = Entity::find();
 
-    let select = select.filter(Column::ChainId.eq(&form.query().chain_id));
-
-    let select = match &form.query().block {
-        Some(block) => select.filter(Column::BlockHash.eq(block)),
-        _ => select,
+    let chain_id = match require_chain(&setting, Some(&form.query().chain_id)) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
     };
 
-    // TODO: filter by address[]
+    let select = select.filter(Column::ChainId.eq(&chain_id));
+
+    let block = match form.query().block.as_ref().map(|block| block.parse()) {
+        Some(Ok(block)) => Some(block),
+        Some(Err(_)) => {
+            return Ok(HttpResponse::BadRequest().json(
+                ErrorResponse::InvalidRequest()
+                    .with_error_description("Malformed parameter `block`")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+        _ => None,
+    };
 
-    let select = select
-        .order_by_desc(Column::Id)
-        .paginate(db.as_ref(), form.size());
+    let select = match block {
+        Some(NumberOrHash::Number(block_number)) => {
+            select.filter(Column::BlockNumber.eq(block_number))
+        }
+        Some(NumberOrHash::Hash(block_hash)) => select.filter(Column::BlockHash.eq(block_hash)),
+        _ => select,
+    };
 
-    let total = select.num_pages().await.unwrap_or_default();
-    response.with_total(total);
+    let select = match &form.query().address {
+        Some(addresses) => match form.query().direction.unwrap_or_default() {
+            TransactionDirection::In => select.filter(Column::ToAddress.is_in(addresses)),
+            TransactionDirection::Out => select.filter(Column::FromAddress.is_in(addresses)),
+            TransactionDirection::Any => select.filter(
+                Column::FromAddress
+                    .is_in(addresses)
+                    .or(Column::ToAddress.is_in(addresses)),
+            ),
+        },
+        _ => select,
+    };
 
-    let transactions = select.fetch_page(form.page()).await.unwrap_or_default();
+    let transactions = paginate(db.as_ref(), select, Column::Id, &form, &mut response).await;
 
     for transaction in transactions {
         let transaction = transaction_manager.dump(&transaction, true).await.unwrap();
@@ -64,9 +89,15 @@ pub async fn handle_transaction(
     form: serde_qs::actix::QsQuery<TransactionFindRequest>,
     provider: web::Data<di::ServiceProvider>,
 ) -> Result<HttpResponse, Error> {
+    let setting = provider.get_required::<Setting>();
     let transaction_manager = provider.get_required::<TransactionManager>();
 
-    let transaction = match transaction_manager.find(&form.chain_id, &path.0).await {
+    let chain_id = match require_chain(&setting, Some(&form.chain_id)) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
+    };
+
+    let transaction = match transaction_manager.find(&chain_id, &path.0).await {
         Some(transaction) => transaction,
         None => {
             return Ok(HttpResponse::NotFound().json(
@@ -0,0 +1,385 @@
+use actix_web::{delete, get, http::header, post, put, web, Error, HttpRequest, HttpResponse};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait as _, QueryFilter};
+
+use crate::{
+    entities::{
+        self,
+        webhook_delivery::{Column, Entity},
+    },
+    handlers::{paginate, require_chain},
+    primitives::{
+        v1::{
+            WebhookDeliveryFindRequest, WebhookDeliveryResponse, WebhookRequest, WebhookResponse,
+        },
+        DataResponse, ErrorResponse, PaginationRequest, PaginationResponse, Setting,
+    },
+    services::{resolve_public, WebhookManager},
+};
+
+// secrets key the delivery signatures and authorize management of the webhook
+const MIN_SECRET_LENGTH: usize = 32;
+
+fn weak_secret(secret: &str) -> bool {
+    secret.trim().len() < MIN_SECRET_LENGTH
+}
+
+fn malformed_url(url: &str) -> bool {
+    !(url.starts_with("https://") || url.starts_with("http://"))
+}
+
+// unroutable_url tells whether url points at loopback, link-local or private
+// addresses, or does not resolve at all
+async fn unroutable_url(url: &str) -> bool {
+    resolve_public(url).await.is_none()
+}
+
+fn bearer(req: &HttpRequest) -> Option<&str> {
+    req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .filter(|token| !token.is_empty())
+}
+
+fn equals(token: &str, secret: &str) -> bool {
+    !secret.is_empty()
+        && token.len() == secret.len()
+        && token
+            .bytes()
+            .zip(secret.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+// webhooks are registered with one of the configured tokens and then managed
+// with their own secret as a bearer token
+fn authorize(req: &HttpRequest, webhook: &entities::webhook::Model) -> bool {
+    bearer(req).is_some_and(|token| equals(token, &webhook.secret))
+}
+
+fn authorize_registration(req: &HttpRequest, setting: &Setting) -> bool {
+    bearer(req).is_some_and(|token| {
+        setting
+            .webhook()
+            .tokens()
+            .iter()
+            .any(|secret| equals(token, secret))
+    })
+}
+
+async fn authorized(
+    req: &HttpRequest,
+    webhook_manager: &WebhookManager,
+    id: i64,
+) -> Result<entities::webhook::Model, HttpResponse> {
+    let webhook = match webhook_manager.get(id).await {
+        Some(webhook) => webhook,
+        None => {
+            return Err(HttpResponse::NotFound().json(
+                ErrorResponse::NotFound()
+                    .with_error_description("Webhook not found")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+    };
+
+    if !authorize(req, &webhook) {
+        return Err(HttpResponse::Unauthorized().json(
+            ErrorResponse::InvalidToken()
+                .with_error_description("Invalid webhook secret")
+                .build()
+                .unwrap(),
+        ));
+    }
+
+    Ok(webhook)
+}
+
+#[post("/webhook")]
+pub async fn handle_webhook_create(
+    req: HttpRequest,
+    form: web::Either<
+        Result<web::Json<WebhookRequest>, Error>,
+        Result<web::Form<WebhookRequest>, Error>,
+    >,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let form = match form {
+        web::Either::Left(Ok(form)) => form.into_inner(),
+        web::Either::Right(Ok(form)) => form.into_inner(),
+        _ => {
+            return Ok(HttpResponse::BadRequest().json(
+                ErrorResponse::InvalidRequest()
+                    .with_error_description("Malformed request")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+    };
+
+    let setting = provider.get_required::<Setting>();
+    let webhook_manager = provider.get_required::<WebhookManager>();
+
+    if !authorize_registration(&req, &setting) {
+        return Ok(HttpResponse::Unauthorized().json(
+            ErrorResponse::InvalidToken()
+                .with_error_description("Invalid webhook token")
+                .build()
+                .unwrap(),
+        ));
+    }
+
+    let chain_id = match require_chain(&setting, form.chain_id.as_deref()) {
+        Ok(chain_id) => chain_id,
+        Err(error) => return Ok(HttpResponse::BadRequest().json(error)),
+    };
+
+    let url = match form.url.clone() {
+        Some(url) => url,
+        _ => {
+            return Ok(HttpResponse::BadRequest().json(
+                ErrorResponse::InvalidRequest()
+                    .with_error_description("Missing mandatory parameter `url`")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+    };
+
+    if malformed_url(&url) {
+        return Ok(HttpResponse::BadRequest().json(
+            ErrorResponse::InvalidRequest()
+                .with_error_description("Malformed webhook url")
+                .build()
+                .unwrap(),
+        ));
+    }
+
+    if unroutable_url(&url).await {
+        return Ok(HttpResponse::BadRequest().json(
+            ErrorResponse::InvalidRequest()
+                .with_error_description("Webhook url must resolve to a public address")
+                .build()
+                .unwrap(),
+        ));
+    }
+
+    if form.secret.as_deref().is_some_and(weak_secret) {
+        return Ok(HttpResponse::BadRequest().json(
+            ErrorResponse::InvalidRequest()
+                .with_error_description(format!(
+                    "Webhook `secret` must be at least {} characters",
+                    MIN_SECRET_LENGTH
+                ))
+                .build()
+                .unwrap(),
+        ));
+    }
+
+    let webhook = match webhook_manager.create(&chain_id, &url, &form).await {
+        Some(webhook) => webhook,
+        None => {
+            return Ok(HttpResponse::InternalServerError().json(ErrorResponse::EmptyServerError()))
+        }
+    };
+
+    let response = match webhook_manager.dump(&webhook, true).await {
+        Some(webhook) => webhook,
+        None => {
+            return Ok(HttpResponse::InternalServerError().json(ErrorResponse::EmptyImpossible()))
+        }
+    };
+
+    let response = DataResponse::<WebhookResponse>::builder()
+        .with_data(response)
+        .build()
+        .unwrap();
+
+    Ok(HttpResponse::Created().json(response))
+}
+
+#[get("/webhook/{id}")]
+pub async fn handle_webhook(
+    req: HttpRequest,
+    path: web::Path<(i64,)>,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let webhook_manager = provider.get_required::<WebhookManager>();
+
+    let webhook = match authorized(&req, &webhook_manager, path.0).await {
+        Ok(webhook) => webhook,
+        Err(response) => return Ok(response),
+    };
+
+    let response = match webhook_manager.dump(&webhook, false).await {
+        Some(webhook) => webhook,
+        None => {
+            return Ok(HttpResponse::InternalServerError().json(ErrorResponse::EmptyImpossible()))
+        }
+    };
+
+    let response = DataResponse::<WebhookResponse>::builder()
+        .with_data(response)
+        .build()
+        .unwrap();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[put("/webhook/{id}")]
+pub async fn handle_webhook_update(
+    req: HttpRequest,
+    path: web::Path<(i64,)>,
+    form: web::Either<
+        Result<web::Json<WebhookRequest>, Error>,
+        Result<web::Form<WebhookRequest>, Error>,
+    >,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let form = match form {
+        web::Either::Left(Ok(form)) => form.into_inner(),
+        web::Either::Right(Ok(form)) => form.into_inner(),
+        _ => {
+            return Ok(HttpResponse::BadRequest().json(
+                ErrorResponse::InvalidRequest()
+                    .with_error_description("Malformed request")
+                    .build()
+                    .unwrap(),
+            ))
+        }
+    };
+
+    let webhook_manager = provider.get_required::<WebhookManager>();
+
+    let webhook = match authorized(&req, &webhook_manager, path.0).await {
+        Ok(webhook) => webhook,
+        Err(response) => return Ok(response),
+    };
+
+    if let Some(chain_id) = &form.chain_id {
+        if chain_id != &webhook.chain_id {
+            return Ok(HttpResponse::BadRequest().json(
+                ErrorResponse::InvalidRequest()
+                    .with_error_description("Webhook `chain_id` cannot be changed")
+                    .build()
+                    .unwrap(),
+            ));
+        }
+    }
+
+    if let Some(url) = form.url.as_deref() {
+        if malformed_url(url) {
+            return Ok(HttpResponse::BadRequest().json(
+                ErrorResponse::InvalidRequest()
+                    .with_error_description("Malformed webhook url")
+                    .build()
+                    .unwrap(),
+            ));
+        }
+
+        if unroutable_url(url).await {
+            return Ok(HttpResponse::BadRequest().json(
+                ErrorResponse::InvalidRequest()
+                    .with_error_description("Webhook url must resolve to a public address")
+                    .build()
+                    .unwrap(),
+            ));
+        }
+    }
+
+    if form.secret.as_deref().is_some_and(weak_secret) {
+        return Ok(HttpResponse::BadRequest().json(
+            ErrorResponse::InvalidRequest()
+                .with_error_description(format!(
+                    "Webhook `secret` must be at least {} characters",
+                    MIN_SECRET_LENGTH
+                ))
+                .build()
+                .unwrap(),
+        ));
+    }
+
+    let rotated = form.secret.is_some();
+
+    let webhook = match webhook_manager.update(webhook, &form).await {
+        Some(webhook) => webhook,
+        None => {
+            return Ok(HttpResponse::InternalServerError().json(ErrorResponse::EmptyServerError()))
+        }
+    };
+
+    let response = match webhook_manager.dump(&webhook, rotated).await {
+        Some(webhook) => webhook,
+        None => {
+            return Ok(HttpResponse::InternalServerError().json(ErrorResponse::EmptyImpossible()))
+        }
+    };
+
+    let response = DataResponse::<WebhookResponse>::builder()
+        .with_data(response)
+        .build()
+        .unwrap();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[delete("/webhook/{id}")]
+pub async fn handle_webhook_delete(
+    req: HttpRequest,
+    path: web::Path<(i64,)>,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let webhook_manager = provider.get_required::<WebhookManager>();
+
+    let webhook = match authorized(&req, &webhook_manager, path.0).await {
+        Ok(webhook) => webhook,
+        Err(response) => return Ok(response),
+    };
+
+    if !webhook_manager.delete(&webhook).await {
+        return Ok(HttpResponse::InternalServerError().json(ErrorResponse::EmptyServerError()));
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[get("/webhook/{id}/delivery")]
+pub async fn handle_webhook_deliveries(
+    req: HttpRequest,
+    path: web::Path<(i64,)>,
+    form: serde_qs::actix::QsQuery<PaginationRequest<WebhookDeliveryFindRequest>>,
+    provider: web::Data<di::ServiceProvider>,
+) -> Result<HttpResponse, Error> {
+    let form = form.into_inner();
+
+    let mut response =
+        PaginationResponse::<WebhookDeliveryFindRequest, WebhookDeliveryResponse>::builder();
+
+    let db = provider.get_required::<DatabaseConnection>();
+    let webhook_manager = provider.get_required::<WebhookManager>();
+
+    let webhook = match authorized(&req, &webhook_manager, path.0).await {
+        Ok(webhook) => webhook,
+        Err(response) => return Ok(response),
+    };
+
+    let select = Entity::find().filter(Column::WebhookId.eq(webhook.id));
+
+    let select = match &form.query().state {
+        Some(state) => select.filter(Column::State.is_in(state.clone())),
+        _ => select,
+    };
+
+    let deliveries = paginate(db.as_ref(), select, Column::Id, &form, &mut response).await;
+
+    for delivery in deliveries {
+        let delivery = webhook_manager.dump_delivery(&delivery).await.unwrap();
+
+        response.append(delivery);
+    }
+
+    let response = response.build().unwrap();
+
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use eos420_primitives::{self as primitives, entities};
 use eos420_services::{self as services};
@@ -12,7 +12,7 @@ use actix_web::{
 
 use clap::Parser as _;
 use di::Injectable as _;
-use sea_orm::{ConnectOptions, Database};
+use sea_orm::{ConnectOptions, Database, DatabaseConnection};
 
 pub mod built_info {
     include!(concat!(env!("OUT_DIR"), "/built.rs"));
@@ -79,6 +79,15 @@ async fn main() -> eyre::Result<()> {
         Arc::new(id)
     }));
 
+    let setting = settings.clone();
+    provider.add(di::singleton_as_self().from(move |_| Arc::new(setting.clone())));
+
+    let capacity = settings.stream().capacity();
+    provider.add(di::singleton_as_self().from(move |_| {
+        let event = services::EventService::new(capacity);
+        Arc::new(event)
+    }));
+
     let mut sea = ConnectOptions::new(settings.database().uri());
     sea.sqlx_logging_level(log::LevelFilter::Debug);
     let db = Database::connect(sea).await?;
@@ -101,9 +110,28 @@ async fn main() -> eyre::Result<()> {
     provider.add(services::ExtrinsicManager::scoped());
     provider.add(services::LockedAssetManager::scoped());
     provider.add(services::TransactionManager::scoped());
+    provider.add(services::WebhookManager::scoped());
 
     let provider = provider.build_provider()?;
 
+    actix_web::rt::spawn(services::watch(
+        provider.get_required::<DatabaseConnection>(),
+        provider.get_required::<services::EventService>(),
+        settings.chains().keys().cloned().collect(),
+        Duration::from_millis(settings.stream().interval()),
+    ));
+
+    actix_web::rt::spawn(services::dispatch(
+        provider.get_required::<services::WebhookManager>(),
+        settings.chains().keys().cloned().collect(),
+        settings.webhook().clone(),
+    ));
+
+    actix_web::rt::spawn(services::deliver(
+        provider.get_required::<services::WebhookManager>(),
+        settings.webhook().clone(),
+    ));
+
     log::info!(
         "starting HTTP server at http://{}",
         settings.core().bind().api()
@@ -160,17 +188,35 @@ async fn main() -> eyre::Result<()> {
             .service(
                 web::scope("/api/v1")
                     .service(handlers::v1::handle_status)
+                    .service(handlers::v1::handle_chains)
+                    .service(handlers::v1::handle_blocks)
+                    .service(handlers::v1::handle_block)
                     .service(handlers::v1::handle_tokens)
                     .service(handlers::v1::handle_token)
                     .service(handlers::v1::handle_holder)
                     .service(handlers::v1::handle_token_deploy)
+                    .service(handlers::v1::handle_token_deploy_state)
+                    .service(handlers::v1::handle_ordinal_encode)
                     .service(handlers::v1::handle_transactions)
                     .service(handlers::v1::handle_transaction)
                     .service(handlers::v1::handle_extrinsics)
+                    .service(handlers::v1::handle_extrinsic_legs)
                     .service(handlers::v1::handle_extrinsic)
+                    .service(handlers::v1::handle_extrinsic_stream)
+                    .service(handlers::v1::handle_extrinsic_export)
+                    .service(handlers::v1::handle_holder_export)
+                    .service(handlers::v1::handle_subscription)
+                    .service(handlers::v1::handle_address)
+                    .service(handlers::v1::handle_address_activity)
                     .service(handlers::v1::handle_assets)
                     .service(handlers::v1::handle_asset)
-                    .service(handlers::v1::handle_nonfungible),
+                    .service(handlers::v1::handle_nonfungible)
+                    .service(handlers::v1::handle_locks)
+                    .service(handlers::v1::handle_webhook_create)
+                    .service(handlers::v1::handle_webhook)
+                    .service(handlers::v1::handle_webhook_update)
+                    .service(handlers::v1::handle_webhook_delete)
+                    .service(handlers::v1::handle_webhook_deliveries),
             )
             .default_service(web::to(HttpResponse::NotFound))
             .wrap(Logger::default())
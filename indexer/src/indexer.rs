@@ -0,0 +1,1062 @@
+use std::{sync::Arc, time::Duration};
+
+use eyre::{eyre, Result};
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait as _, ColumnTrait as _, ConnectionTrait, DatabaseConnection,
+    DatabaseTransaction, EntityTrait as _, QueryFilter as _, QueryOrder as _, Set,
+    TransactionTrait as _,
+};
+use time::OffsetDateTime;
+
+use crate::{
+    entities::{self, BlockState, ContractState, DropReason, ExtrinsicOperation},
+    primitives::{bigint::ToPrimitive as _, ordinal::Ordinal, ChainSetting},
+    rpc::Rpc,
+    services::{self, verify_ordinal, IdService},
+    types,
+};
+
+// hex encoding of the `data:` prefix every inscription starts with
+const INSCRIPTION_PREFIX: &str = "0x646174613a";
+
+pub fn normalize(address: &str) -> String {
+    format!(
+        "0x{}",
+        address.trim_start_matches("0x").to_ascii_lowercase()
+    )
+}
+
+pub struct Indexer {
+    chain_id: String,
+    chain: ChainSetting,
+    db: Arc<DatabaseConnection>,
+    id: Arc<IdService>,
+    rpc: Rpc,
+}
+
+impl Indexer {
+    pub fn new(
+        chain_id: &str,
+        chain: ChainSetting,
+        db: Arc<DatabaseConnection>,
+        id: Arc<IdService>,
+        rpc: Rpc,
+    ) -> Self {
+        Self {
+            chain_id: chain_id.to_owned(),
+            chain,
+            db,
+            id,
+            rpc,
+        }
+    }
+
+    pub fn chain_id(&self) -> &str {
+        &self.chain_id
+    }
+
+    pub async fn run(&self, interval: Duration) {
+        loop {
+            if let Err(err) = self.sync().await {
+                log::warn!("failed to index chain {}: {}", self.chain_id, err);
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    pub async fn sync(&self) -> Result<()> {
+        let head = self.rpc.block_number().await?;
+
+        let mut next = self.next(head).await?;
+        while next <= head {
+            let block = self
+                .rpc
+                .block(next)
+                .await?
+                .ok_or_else(|| eyre!("block {} is not available", next))?;
+
+            let parent = match next.checked_sub(1) {
+                Some(parent) => self.canonical(parent as i64).await?,
+                None => None,
+            };
+
+            // a parent we do not know means our head is no longer canonical
+            if let Some(parent) = parent {
+                if parent.block_hash != block.parent_hash {
+                    next = self.reorg(parent.block_number).await? + 1;
+                    continue;
+                }
+            }
+
+            self.index(&block).await?;
+
+            next += 1;
+        }
+
+        self.finalize(head).await
+    }
+
+    async fn next(&self, head: u64) -> Result<u64> {
+        let latest = entities::block::Entity::find()
+            .filter(entities::block::Column::ChainId.eq(&self.chain_id))
+            .filter(entities::block::Column::State.ne(BlockState::Dropped))
+            .order_by_desc(entities::block::Column::BlockNumber)
+            .one(self.db.as_ref())
+            .await?;
+
+        Ok(match latest {
+            // a block left pending or indexing was interrupted and is indexed again
+            Some(block) if matches!(block.state, BlockState::Pending | BlockState::Indexing) => {
+                block.block_number as u64
+            }
+            Some(block) => block.block_number as u64 + 1,
+            None => self.chain.start_block().unwrap_or(head),
+        })
+    }
+
+    async fn canonical(&self, number: i64) -> Result<Option<entities::block::Model>> {
+        Ok(entities::block::Entity::find()
+            .filter(entities::block::Column::ChainId.eq(&self.chain_id))
+            .filter(entities::block::Column::BlockNumber.eq(number))
+            .filter(entities::block::Column::State.ne(BlockState::Dropped))
+            .one(self.db.as_ref())
+            .await?)
+    }
+
+    // reorg walks back from number until our block matches the node again and
+    // reverts everything above that fork point, which is returned
+    async fn reorg(&self, number: i64) -> Result<u64> {
+        let mut fork = number;
+
+        while let Some(stored) = self.canonical(fork).await? {
+            let block = self
+                .rpc
+                .block(fork as u64)
+                .await?
+                .ok_or_else(|| eyre!("block {} is not available", fork))?;
+
+            if block.hash == stored.block_hash {
+                break;
+            }
+
+            if stored.state == BlockState::Finalized {
+                return Err(eyre!(
+                    "chain {} reorganized below finalized block {}",
+                    self.chain_id,
+                    fork
+                ));
+            }
+
+            fork -= 1;
+        }
+
+        self.revert(fork).await?;
+
+        log::warn!(
+            "chain {} reorganized, reverted blocks above {}",
+            self.chain_id,
+            fork
+        );
+
+        Ok(fork as u64)
+    }
+
+    // revert orphans every block above fork together with its transactions and
+    // extrinsics and rolls back the ledger changes they caused
+    async fn revert(&self, fork: i64) -> Result<()> {
+        let now = OffsetDateTime::now_utc();
+        let revision = self.id.next_id();
+
+        let txn = self.db.begin().await?;
+
+        let hashes = entities::block::Entity::find()
+            .filter(entities::block::Column::ChainId.eq(&self.chain_id))
+            .filter(entities::block::Column::BlockNumber.gt(fork))
+            .filter(entities::block::Column::State.ne(BlockState::Dropped))
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|block| block.block_hash)
+            .collect::<Vec<_>>();
+
+        let applied = entities::extrinsic::Entity::find()
+            .filter(entities::extrinsic::Column::ChainId.eq(&self.chain_id))
+            .filter(entities::extrinsic::Column::BlockHash.is_in(hashes.clone()))
+            .filter(entities::extrinsic::Column::State.eq(BlockState::Confirmed))
+            .all(&txn)
+            .await?;
+
+        services::revert(&txn, &self.id, &applied).await?;
+
+        entities::extrinsic::Entity::update_many()
+            .col_expr(
+                entities::extrinsic::Column::State,
+                Expr::value(BlockState::Dropped),
+            )
+            .col_expr(
+                entities::extrinsic::Column::DropReason,
+                Expr::value(DropReason::TransactionDropped),
+            )
+            .col_expr(entities::extrinsic::Column::Revision, Expr::value(revision))
+            .col_expr(entities::extrinsic::Column::UpdatedAt, Expr::value(now))
+            .filter(entities::extrinsic::Column::ChainId.eq(&self.chain_id))
+            .filter(entities::extrinsic::Column::BlockHash.is_in(hashes.clone()))
+            .filter(entities::extrinsic::Column::State.ne(BlockState::Dropped))
+            .exec(&txn)
+            .await?;
+
+        entities::transaction::Entity::update_many()
+            .col_expr(
+                entities::transaction::Column::State,
+                Expr::value(BlockState::Dropped),
+            )
+            .col_expr(entities::transaction::Column::UpdatedAt, Expr::value(now))
+            .filter(entities::transaction::Column::ChainId.eq(&self.chain_id))
+            .filter(entities::transaction::Column::BlockHash.is_in(hashes.clone()))
+            .exec(&txn)
+            .await?;
+
+        entities::block::Entity::update_many()
+            .col_expr(
+                entities::block::Column::State,
+                Expr::value(BlockState::Dropped),
+            )
+            .col_expr(entities::block::Column::Revision, Expr::value(revision))
+            .col_expr(entities::block::Column::UpdatedAt, Expr::value(now))
+            .filter(entities::block::Column::ChainId.eq(&self.chain_id))
+            .filter(entities::block::Column::BlockHash.is_in(hashes))
+            .exec(&txn)
+            .await?;
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn index(&self, block: &types::Block) -> Result<()> {
+        let number = block
+            .number
+            .to_i64()
+            .ok_or_else(|| eyre!("block number out of range"))?;
+        let now = OffsetDateTime::now_utc();
+
+        self.discard(number).await?;
+
+        let mined_at = block
+            .timestamp
+            .to_i64()
+            .and_then(|timestamp| OffsetDateTime::from_unix_timestamp(timestamp).ok());
+
+        let row = entities::block::ActiveModel {
+            id: Set(self.id.next_id()),
+            chain_id: Set(self.chain_id.clone()),
+            block_number: Set(number),
+            block_hash: Set(block.hash.clone()),
+            parent_hash: Set(block.parent_hash.clone()),
+            transaction_count: Set(0),
+            extrinsic_count: Set(0),
+            state: Set(BlockState::Pending),
+            revision: Set(self.id.next_id()),
+            mined_at: Set(mined_at),
+            finalized_at: Set(None),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+        }
+        .insert(self.db.as_ref())
+        .await?;
+
+        // the block stays pending while its receipts are fetched, they are fetched
+        // before the database transaction so that it is not held across rpc calls
+        let mut receipts = Vec::new();
+        for transaction in &block.transactions {
+            if transaction.input.starts_with(INSCRIPTION_PREFIX) {
+                receipts.push((transaction, self.rpc.receipt(&transaction.hash).await?));
+            }
+        }
+
+        let mut row: entities::block::ActiveModel = row.into();
+        row.state = Set(BlockState::Indexing);
+        row.revision = Set(self.id.next_id());
+        row.updated_at = Set(Some(OffsetDateTime::now_utc()));
+        let row = row.update(self.db.as_ref()).await?;
+
+        let txn = self.db.begin().await?;
+
+        let mut transaction_count = 0;
+        let mut extrinsic_count = 0;
+
+        for (transaction, receipt) in receipts {
+            self.transaction(&txn, block, transaction, receipt.as_ref())
+                .await?;
+            transaction_count += 1;
+
+            if let Ok(ordinal) = Ordinal::parse(&transaction.input) {
+                let succeeded = receipt.as_ref().is_some_and(types::Receipt::succeeded);
+
+                extrinsic_count += self
+                    .extrinsic(&txn, block, transaction, &ordinal, succeeded)
+                    .await?;
+            }
+        }
+
+        let mut row: entities::block::ActiveModel = row.into();
+        row.transaction_count = Set(transaction_count);
+        row.extrinsic_count = Set(extrinsic_count);
+        row.state = Set(BlockState::Confirmed);
+        row.revision = Set(self.id.next_id());
+        row.updated_at = Set(Some(OffsetDateTime::now_utc()));
+        row.update(&txn).await?;
+
+        txn.commit().await?;
+
+        log::debug!(
+            "indexed block {} of chain {} with {} extrinsics",
+            number,
+            self.chain_id,
+            extrinsic_count
+        );
+
+        Ok(())
+    }
+
+    // discard removes the row a previous interrupted attempt left for the block
+    // number, orphaned blocks are kept
+    async fn discard(&self, number: i64) -> Result<()> {
+        entities::block::Entity::delete_many()
+            .filter(entities::block::Column::ChainId.eq(&self.chain_id))
+            .filter(entities::block::Column::BlockNumber.eq(number))
+            .filter(
+                entities::block::Column::State.is_in([BlockState::Pending, BlockState::Indexing]),
+            )
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn transaction<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        block: &types::Block,
+        transaction: &types::Transaction,
+        receipt: Option<&types::Receipt>,
+    ) -> Result<()> {
+        let now = OffsetDateTime::now_utc();
+
+        let value_used = receipt
+            .map(|receipt| {
+                receipt.gas_used.clone() * receipt.effective_gas_price.clone().unwrap_or_default()
+            })
+            .unwrap_or_default();
+
+        let state = match receipt {
+            Some(receipt) if receipt.succeeded() => BlockState::Confirmed,
+            _ => BlockState::Dropped,
+        };
+
+        entities::transaction::ActiveModel {
+            id: Set(self.id.next_id()),
+            chain_id: Set(self.chain_id.clone()),
+            block_number: Set(block.number.to_i64()),
+            block_hash: Set(Some(block.hash.clone())),
+            tx_index: Set(transaction.transaction_index.to_i64()),
+            tx_hash: Set(transaction.hash.clone()),
+            from_address: Set(normalize(&transaction.from)),
+            to_address: Set(transaction.to.as_deref().map(normalize)),
+            value: Set(format!("{:#x}", transaction.value)),
+            value_used: Set(format!("{:#x}", value_used)),
+            state: Set(state),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+        }
+        .insert(db)
+        .await?;
+
+        Ok(())
+    }
+
+    // extrinsic records the inscription of a transaction, a batch is expanded into
+    // one extrinsic per operand and returns how many were recorded
+    async fn extrinsic(
+        &self,
+        db: &DatabaseTransaction,
+        block: &types::Block,
+        transaction: &types::Transaction,
+        ordinal: &Ordinal,
+        succeeded: bool,
+    ) -> Result<i64> {
+        let now = OffsetDateTime::now_utc();
+
+        let from = normalize(&transaction.from);
+        let target = transaction.to.as_deref().map(normalize).unwrap_or_default();
+
+        let drop_reason = if succeeded {
+            self.check(db, transaction, ordinal, &from, &target).await?
+        } else {
+            Some(DropReason::TransactionDropped)
+        };
+
+        let state = match drop_reason {
+            Some(_) => BlockState::Dropped,
+            None => BlockState::Confirmed,
+        };
+
+        // deploys carry no operand and still record a single extrinsic
+        let operands = match ordinal.operands() {
+            Some(operands) => operands.into_iter().map(Some).collect(),
+            None => vec![None],
+        };
+
+        let mut legs = Vec::with_capacity(operands.len());
+        for (index, operand) in operands.into_iter().enumerate() {
+            let (from_address, to_address) = match ordinal.operation {
+                ExtrinsicOperation::Mint => (target.clone(), from.clone()),
+                // a stake is delegated to its operand, the contract by default
+                ExtrinsicOperation::Stake => (
+                    from.clone(),
+                    operand
+                        .and_then(|operand| operand.to.as_deref())
+                        .map_or(target.clone(), normalize),
+                ),
+                ExtrinsicOperation::Transfer | ExtrinsicOperation::Unlock => (
+                    from.clone(),
+                    operand
+                        .and_then(|operand| operand.to.as_deref())
+                        .map(normalize)
+                        .unwrap_or_default(),
+                ),
+                _ => (from.clone(), target.clone()),
+            };
+
+            let value = operand
+                .and_then(|operand| operand.amount.clone().or(operand.id.clone()))
+                .or(ordinal.max_supply.clone())
+                .unwrap_or_default();
+
+            legs.push(entities::extrinsic::Model {
+                id: self.id.next_id(),
+                chain_id: self.chain_id.clone(),
+                block_number: block.number.to_i64(),
+                block_hash: Some(block.hash.clone()),
+                tx_index: transaction.transaction_index.to_i64(),
+                tx_hash: transaction.hash.clone(),
+                index: index as i64,
+                asset_id: ordinal.tick.clone(),
+                protocol: ordinal.protocol,
+                from_address,
+                to_address,
+                operation: ordinal.operation,
+                value,
+                context: Some(ordinal.encode()),
+                state,
+                drop_reason,
+                revision: self.id.next_id(),
+                created_at: Some(now),
+                updated_at: Some(now),
+            });
+        }
+
+        let timestamp = block.timestamp.to_i64().unwrap_or_default();
+
+        // the legs settle together, when one of them is dropped the others are
+        // rolled back and dropped for the same reason
+        let savepoint = db.begin().await?;
+        services::apply(&savepoint, &self.id, &mut legs, timestamp).await?;
+
+        match legs.iter().find_map(|leg| leg.drop_reason) {
+            Some(reason) => {
+                savepoint.rollback().await?;
+
+                for leg in &mut legs {
+                    leg.state = BlockState::Dropped;
+                    leg.drop_reason = Some(reason);
+                }
+            }
+            None => savepoint.commit().await?,
+        }
+
+        let count = legs.len() as i64;
+
+        let deployed = ordinal.operation == ExtrinsicOperation::Deploy
+            && legs.iter().all(|leg| leg.drop_reason.is_none());
+
+        for leg in legs {
+            entities::extrinsic::ActiveModel::from(leg)
+                .reset_all()
+                .insert(db)
+                .await?;
+        }
+
+        if deployed {
+            self.deploy(db, transaction, ordinal, &from).await?;
+        }
+
+        Ok(count)
+    }
+
+    // check applies the rules that do not depend on balances and returns why the
+    // inscription is dropped, if it is
+    async fn check<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        transaction: &types::Transaction,
+        ordinal: &Ordinal,
+        from: &str,
+        target: &str,
+    ) -> Result<Option<DropReason>> {
+        if let Err(reason) = ordinal.validate() {
+            return Ok(Some(reason));
+        }
+
+        if !self.chain.protocols().contains(&ordinal.protocol) {
+            return Ok(Some(DropReason::ProtocolMismatch));
+        }
+
+        let contract = entities::contract::Entity::find()
+            .filter(entities::contract::Column::ChainId.eq(&self.chain_id))
+            .filter(entities::contract::Column::AssetId.eq(&ordinal.tick))
+            .order_by_desc(entities::contract::Column::Id)
+            .one(db)
+            .await?;
+
+        if ordinal.operation == ExtrinsicOperation::Deploy {
+            if target != normalize(self.chain.fee_recipient())
+                || &transaction.value < self.chain.fee()
+            {
+                return Ok(Some(DropReason::FeeInsufficient));
+            }
+
+            let now = OffsetDateTime::now_utc();
+
+            return Ok(match contract {
+                None => None,
+                Some(contract)
+                    if contract.state == ContractState::Pending
+                        && (contract
+                            .expires_at
+                            .is_some_and(|expires_at| expires_at <= now)
+                            || contract
+                                .owner
+                                .as_deref()
+                                .is_none_or(|owner| normalize(owner) == from)) =>
+                {
+                    None
+                }
+                Some(_) => Some(DropReason::ExtrinsicConflicted),
+            });
+        }
+
+        let contract = match contract {
+            Some(contract)
+                if matches!(
+                    contract.state,
+                    ContractState::Deploying | ContractState::Deployed
+                ) =>
+            {
+                contract
+            }
+            _ => return Ok(Some(DropReason::OperationInvalid)),
+        };
+
+        if contract.protocol != ordinal.protocol {
+            return Ok(Some(DropReason::ProtocolMismatch));
+        }
+
+        if normalize(&contract.address) != target {
+            return Ok(Some(DropReason::OperationInvalid));
+        }
+
+        Ok(verify_ordinal(&contract, ordinal, from).err())
+    }
+
+    // deploy claims the token name for the deployer until the deploy is finalized
+    async fn deploy<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        transaction: &types::Transaction,
+        ordinal: &Ordinal,
+        from: &str,
+    ) -> Result<()> {
+        let now = OffsetDateTime::now_utc();
+
+        let contract = entities::contract::Entity::find()
+            .filter(entities::contract::Column::ChainId.eq(&self.chain_id))
+            .filter(entities::contract::Column::AssetId.eq(&ordinal.tick))
+            .order_by_desc(entities::contract::Column::Id)
+            .one(db)
+            .await?;
+
+        let reserved = contract.is_some();
+
+        let mut contract: entities::contract::ActiveModel = match contract {
+            Some(contract) => contract.into(),
+            None => {
+                let class = entities::class::ActiveModel {
+                    id: Set(self.id.next_id()),
+                    r#type: Set(ordinal.protocol.into()),
+                    name: Set(ordinal.name.clone().unwrap_or(ordinal.tick.clone())),
+                    symbol: Set(ordinal.tick.clone()),
+                    owner: Set(Some(from.to_owned())),
+                    description: Set(String::new()),
+                    cover_image_uri: Set(ordinal.uri.clone().unwrap_or_default()),
+                    image_uri_template: Set(None),
+                    created_at: Set(Some(now)),
+                    updated_at: Set(Some(now)),
+                }
+                .insert(db)
+                .await?;
+
+                entities::contract::ActiveModel {
+                    id: Set(self.id.next_id()),
+                    class_id: Set(class.id),
+                    chain_id: Set(self.chain_id.clone()),
+                    asset_id: Set(ordinal.tick.clone()),
+                    address: Set(self.chain.fee_recipient().to_owned()),
+                    protocol: Set(ordinal.protocol),
+                    decimals: Set(None),
+                    identifier: Set(None),
+                    deployed_at: Set(None),
+                    verifying_key: Set(None),
+                    created_at: Set(Some(now)),
+                    ..Default::default()
+                }
+            }
+        };
+
+        contract.owner = Set(Some(from.to_owned()));
+        contract.protocol = Set(ordinal.protocol);
+        contract.max_supply = Set(ordinal.max_supply.clone());
+        contract.mint_limit = Set(ordinal.mint_limit.clone());
+        contract.not_before = Set(ordinal
+            .not_before
+            .as_deref()
+            .and_then(|not_before| not_before.parse().ok()));
+        contract.tx_hash = Set(Some(transaction.hash.clone()));
+        contract.state = Set(ContractState::Deploying);
+        contract.expires_at = Set(None);
+        contract.updated_at = Set(Some(now));
+
+        // the id of a contract deployed without reservation is already set, so it
+        // is inserted explicitly rather than saved
+        if reserved {
+            contract.update(db).await?;
+        } else {
+            contract.insert(db).await?;
+        }
+
+        Ok(())
+    }
+
+    // finalize moves everything deeper than the finality depth to finalized
+    pub async fn finalize(&self, head: u64) -> Result<()> {
+        let cutoff = match head.checked_sub(self.chain.finality()) {
+            Some(cutoff) => cutoff as i64,
+            None => return Ok(()),
+        };
+
+        let now = OffsetDateTime::now_utc();
+        let revision = self.id.next_id();
+
+        let txn = self.db.begin().await?;
+
+        let deploys = entities::extrinsic::Entity::find()
+            .filter(entities::extrinsic::Column::ChainId.eq(&self.chain_id))
+            .filter(entities::extrinsic::Column::State.eq(BlockState::Confirmed))
+            .filter(entities::extrinsic::Column::Operation.eq(ExtrinsicOperation::Deploy))
+            .filter(entities::extrinsic::Column::BlockNumber.lte(cutoff))
+            .all(&txn)
+            .await?;
+
+        entities::block::Entity::update_many()
+            .col_expr(
+                entities::block::Column::State,
+                Expr::value(BlockState::Finalized),
+            )
+            .col_expr(entities::block::Column::FinalizedAt, Expr::value(now))
+            .col_expr(entities::block::Column::Revision, Expr::value(revision))
+            .col_expr(entities::block::Column::UpdatedAt, Expr::value(now))
+            .filter(entities::block::Column::ChainId.eq(&self.chain_id))
+            .filter(entities::block::Column::State.eq(BlockState::Confirmed))
+            .filter(entities::block::Column::BlockNumber.lte(cutoff))
+            .exec(&txn)
+            .await?;
+
+        entities::transaction::Entity::update_many()
+            .col_expr(
+                entities::transaction::Column::State,
+                Expr::value(BlockState::Finalized),
+            )
+            .col_expr(entities::transaction::Column::UpdatedAt, Expr::value(now))
+            .filter(entities::transaction::Column::ChainId.eq(&self.chain_id))
+            .filter(entities::transaction::Column::State.eq(BlockState::Confirmed))
+            .filter(entities::transaction::Column::BlockNumber.lte(cutoff))
+            .exec(&txn)
+            .await?;
+
+        entities::extrinsic::Entity::update_many()
+            .col_expr(
+                entities::extrinsic::Column::State,
+                Expr::value(BlockState::Finalized),
+            )
+            .col_expr(entities::extrinsic::Column::Revision, Expr::value(revision))
+            .col_expr(entities::extrinsic::Column::UpdatedAt, Expr::value(now))
+            .filter(entities::extrinsic::Column::ChainId.eq(&self.chain_id))
+            .filter(entities::extrinsic::Column::State.eq(BlockState::Confirmed))
+            .filter(entities::extrinsic::Column::BlockNumber.lte(cutoff))
+            .exec(&txn)
+            .await?;
+
+        for deploy in deploys {
+            entities::contract::Entity::update_many()
+                .col_expr(
+                    entities::contract::Column::State,
+                    Expr::value(ContractState::Deployed),
+                )
+                .col_expr(entities::contract::Column::DeployedAt, Expr::value(now))
+                .col_expr(entities::contract::Column::UpdatedAt, Expr::value(now))
+                .filter(entities::contract::Column::ChainId.eq(&self.chain_id))
+                .filter(entities::contract::Column::AssetId.eq(&deploy.asset_id))
+                .filter(entities::contract::Column::TxHash.eq(&deploy.tx_hash))
+                .filter(entities::contract::Column::State.eq(ContractState::Deploying))
+                .exec(&txn)
+                .await?;
+        }
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use migration::{Migrator, MigratorTrait as _};
+    use sea_orm::{ConnectOptions, Database};
+
+    use super::*;
+    use crate::rpc::{FixtureTransport, Rpc};
+
+    const CHAIN_ID: &str = "1";
+    const FEE_RECIPIENT: &str = "0xfefefefefefefefefefefefefefefefefefefefe";
+    const MINTER: &str = "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+
+    async fn database() -> Arc<DatabaseConnection> {
+        // every connection to an in-memory database opens a database of its own
+        let mut options = ConnectOptions::new("sqlite::memory:");
+        options.max_connections(1);
+
+        let db = Database::connect(options).await.unwrap();
+        Migrator::up(&db, None).await.unwrap();
+
+        Arc::new(db)
+    }
+
+    // indexer follows the node recorded under fixtures/<fixture>
+    fn indexer(db: &Arc<DatabaseConnection>, id: &Arc<IdService>, fixture: &str) -> Indexer {
+        let chain = serde_json::from_value::<ChainSetting>(serde_json::json!({
+            "name": "test",
+            "fee": "0x1",
+            "fee_recipient": FEE_RECIPIENT,
+            "finality": 2,
+            "start_block": 1,
+        }))
+        .unwrap();
+
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures")
+            .join(fixture);
+
+        Indexer::new(
+            CHAIN_ID,
+            chain,
+            db.clone(),
+            id.clone(),
+            Rpc::new(FixtureTransport::new(fixtures)),
+        )
+    }
+
+    async fn blocks(db: &DatabaseConnection) -> Vec<entities::block::Model> {
+        entities::block::Entity::find()
+            .order_by_asc(entities::block::Column::BlockNumber)
+            .order_by_asc(entities::block::Column::Id)
+            .all(db)
+            .await
+            .unwrap()
+    }
+
+    async fn extrinsics(db: &DatabaseConnection) -> Vec<entities::extrinsic::Model> {
+        entities::extrinsic::Entity::find()
+            .order_by_asc(entities::extrinsic::Column::BlockNumber)
+            .order_by_asc(entities::extrinsic::Column::TxIndex)
+            .all(db)
+            .await
+            .unwrap()
+    }
+
+    async fn balance(db: &DatabaseConnection, address: &str) -> Option<String> {
+        entities::asset::Entity::find()
+            .filter(entities::asset::Column::AssetId.eq("zk"))
+            .filter(entities::asset::Column::Address.eq(address))
+            .one(db)
+            .await
+            .unwrap()
+            .map(|asset| asset.value)
+    }
+
+    #[tokio::test]
+    async fn sync_indexes_blocks_transactions_and_extrinsics() {
+        let db = database().await;
+        let id = Arc::new(IdService::new(1));
+
+        indexer(&db, &id, "linear/0").sync().await.unwrap();
+
+        let blocks = blocks(&db).await;
+        assert_eq!(
+            blocks
+                .iter()
+                .map(|block| (block.block_number, block.state, block.extrinsic_count))
+                .collect::<Vec<_>>(),
+            [(1, BlockState::Confirmed, 1), (2, BlockState::Confirmed, 2)]
+        );
+
+        // only inscriptions are recorded, the plain transfer of block 1 is not
+        let transactions = entities::transaction::Entity::find()
+            .order_by_asc(entities::transaction::Column::BlockNumber)
+            .order_by_asc(entities::transaction::Column::TxIndex)
+            .all(db.as_ref())
+            .await
+            .unwrap();
+        assert_eq!(
+            transactions
+                .iter()
+                .map(|transaction| (transaction.block_number, transaction.state))
+                .collect::<Vec<_>>(),
+            [
+                (Some(1), BlockState::Confirmed),
+                (Some(2), BlockState::Confirmed),
+                (Some(2), BlockState::Dropped),
+            ]
+        );
+
+        let extrinsics = extrinsics(&db).await;
+        assert_eq!(
+            extrinsics
+                .iter()
+                .map(|extrinsic| (extrinsic.operation, extrinsic.state, extrinsic.drop_reason))
+                .collect::<Vec<_>>(),
+            [
+                (ExtrinsicOperation::Deploy, BlockState::Confirmed, None),
+                (ExtrinsicOperation::Mint, BlockState::Confirmed, None),
+                (
+                    ExtrinsicOperation::Mint,
+                    BlockState::Dropped,
+                    Some(DropReason::TransactionDropped)
+                ),
+            ]
+        );
+        assert_eq!(extrinsics[1].to_address, MINTER);
+
+        let contract = entities::contract::Entity::find()
+            .one(db.as_ref())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(contract.state, ContractState::Deploying);
+        assert_eq!(contract.minted, "0x64");
+
+        assert_eq!(balance(&db, MINTER).await.as_deref(), Some("0x64"));
+    }
+
+    #[tokio::test]
+    async fn sync_moves_blocks_from_indexing_to_finalized() {
+        let db = database().await;
+        let id = Arc::new(IdService::new(1));
+
+        // a block left indexing by an interrupted run is indexed again
+        entities::block::ActiveModel {
+            id: Set(id.next_id()),
+            chain_id: Set(CHAIN_ID.to_owned()),
+            block_number: Set(1),
+            block_hash: Set("0x01".to_owned()),
+            parent_hash: Set("0x00".to_owned()),
+            transaction_count: Set(0),
+            extrinsic_count: Set(0),
+            state: Set(BlockState::Indexing),
+            revision: Set(id.next_id()),
+            mined_at: Set(None),
+            finalized_at: Set(None),
+            created_at: Set(None),
+            updated_at: Set(None),
+        }
+        .insert(db.as_ref())
+        .await
+        .unwrap();
+
+        indexer(&db, &id, "linear/0").sync().await.unwrap();
+
+        let confirmed = blocks(&db).await;
+        assert_eq!(
+            confirmed
+                .iter()
+                .map(|block| (block.block_number, block.state))
+                .collect::<Vec<_>>(),
+            [(1, BlockState::Confirmed), (2, BlockState::Confirmed)]
+        );
+
+        indexer(&db, &id, "linear/1").sync().await.unwrap();
+
+        // blocks two below the head are final, the newer ones stay confirmed
+        let finalized = blocks(&db).await;
+        assert_eq!(
+            finalized
+                .iter()
+                .map(|block| (block.block_number, block.state))
+                .collect::<Vec<_>>(),
+            [
+                (1, BlockState::Finalized),
+                (2, BlockState::Finalized),
+                (3, BlockState::Confirmed),
+                (4, BlockState::Confirmed),
+            ]
+        );
+        assert!(finalized
+            .iter()
+            .zip(&confirmed)
+            .all(
+                |(finalized, confirmed)| finalized.revision > confirmed.revision
+                    && finalized.finalized_at.is_some()
+            ));
+
+        assert_eq!(
+            extrinsics(&db)
+                .await
+                .iter()
+                .map(|extrinsic| extrinsic.state)
+                .collect::<Vec<_>>(),
+            [
+                BlockState::Finalized,
+                BlockState::Finalized,
+                BlockState::Dropped
+            ]
+        );
+
+        let contract = entities::contract::Entity::find()
+            .one(db.as_ref())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(contract.state, ContractState::Deployed);
+    }
+
+    #[tokio::test]
+    async fn sync_reverts_and_reindexes_reorganized_blocks() {
+        let db = database().await;
+        let id = Arc::new(IdService::new(1));
+
+        indexer(&db, &id, "linear/0").sync().await.unwrap();
+
+        let before = extrinsics(&db).await;
+
+        // the node replaced block 2 with one whose mint goes to another address
+        indexer(&db, &id, "reorg/1").sync().await.unwrap();
+
+        let blocks = blocks(&db).await;
+        assert_eq!(
+            blocks
+                .iter()
+                .map(|block| (block.block_number, block.state))
+                .collect::<Vec<_>>(),
+            [
+                (1, BlockState::Finalized),
+                (2, BlockState::Dropped),
+                (2, BlockState::Confirmed),
+                (3, BlockState::Confirmed),
+            ]
+        );
+
+        let orphan = &blocks[1];
+        assert_eq!(orphan.block_hash, before[1].block_hash.clone().unwrap());
+
+        let extrinsics = extrinsics(&db).await;
+        let orphaned = extrinsics
+            .iter()
+            .filter(|extrinsic| extrinsic.block_hash.as_ref() == Some(&orphan.block_hash))
+            .collect::<Vec<_>>();
+
+        // the applied mint is dropped by the revert, the failed one keeps its revision
+        assert_eq!(orphaned.len(), 2);
+        assert_eq!(orphaned[0].state, BlockState::Dropped);
+        assert_eq!(
+            orphaned[0].drop_reason,
+            Some(DropReason::TransactionDropped)
+        );
+        assert_eq!(orphaned[0].revision, orphan.revision);
+        assert_eq!(orphaned[1].revision, before[2].revision);
+
+        let transactions = entities::transaction::Entity::find()
+            .filter(entities::transaction::Column::BlockHash.eq(&orphan.block_hash))
+            .all(db.as_ref())
+            .await
+            .unwrap();
+        assert!(transactions
+            .iter()
+            .all(|transaction| transaction.state == BlockState::Dropped));
+
+        // the ledger holds only the mint of the canonical block
+        assert_eq!(balance(&db, MINTER).await, None);
+        assert_eq!(
+            balance(&db, "0xcccccccccccccccccccccccccccccccccccccccc")
+                .await
+                .as_deref(),
+            Some("0x32")
+        );
+
+        let history = entities::asset_change::Entity::find()
+            .all(db.as_ref())
+            .await
+            .unwrap();
+        assert!(history.iter().all(|change| change.address != MINTER));
+
+        let contract = entities::contract::Entity::find()
+            .one(db.as_ref())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(contract.minted, "0x32");
+    }
+
+    #[tokio::test]
+    async fn sync_aborts_reorgs_the_ledger_cannot_cover() {
+        let db = database().await;
+        let id = Arc::new(IdService::new(1));
+
+        indexer(&db, &id, "linear/0").sync().await.unwrap();
+
+        // the balance the orphaned mint credited is gone
+        entities::asset::Entity::delete_many()
+            .filter(entities::asset::Column::Address.eq(MINTER))
+            .exec(db.as_ref())
+            .await
+            .unwrap();
+
+        let before = blocks(&db).await;
+
+        assert!(indexer(&db, &id, "reorg/1").sync().await.is_err());
+
+        // nothing of the revert is kept
+        let after = blocks(&db).await;
+        assert_eq!(
+            after
+                .iter()
+                .map(|block| (block.block_number, block.state, block.revision))
+                .collect::<Vec<_>>(),
+            before
+                .iter()
+                .map(|block| (block.block_number, block.state, block.revision))
+                .collect::<Vec<_>>()
+        );
+    }
+}
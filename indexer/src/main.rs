@@ -0,0 +1,136 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use eos420_primitives::{self as primitives, entities};
+use eos420_services::{self as services};
+use migration::{Migrator, MigratorTrait as _};
+
+use clap::Parser;
+use sea_orm::{ConnectOptions, Database};
+
+mod indexer;
+mod rpc;
+mod types;
+
+#[derive(Debug, Parser)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    #[arg(short, long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    // chain restricts indexing to a single configured chain
+    #[arg(long, value_name = "CHAIN")]
+    chain: Option<String>,
+
+    // fixtures replays recorded JSON-RPC responses instead of calling a node
+    #[arg(long, value_name = "DIR")]
+    fixtures: Option<PathBuf>,
+
+    // once indexes up to the current head and exits
+    #[arg(long)]
+    once: bool,
+}
+
+#[tokio::main]
+async fn main() -> eyre::Result<()> {
+    let cli = Cli::parse();
+
+    let config = match cli.config {
+        Some(config) => config::File::from(config),
+        None => config::File::with_name("settings").required(false),
+    };
+
+    let config = config::Config::builder()
+        .add_source(config)
+        .add_source(
+            config::Environment::with_prefix("EOS420")
+                .separator("_")
+                .ignore_empty(true)
+                .try_parsing(true),
+        )
+        .build()
+        .unwrap();
+
+    let settings = config.try_deserialize::<primitives::Setting>().unwrap();
+
+    env_logger::builder()
+        .filter_level(
+            settings
+                .core()
+                .log()
+                .parse()
+                .unwrap_or(log::LevelFilter::Info),
+        )
+        .init();
+
+    log::info!("EOS420/Indexer Version {}", env!("CARGO_PKG_VERSION"));
+
+    let machine_id = settings.core().machine_id();
+    if machine_id == 0xFFFF {
+        log::warn!("application is using default machine id");
+    }
+
+    let id = Arc::new(services::IdService::new(machine_id));
+
+    let mut sea = ConnectOptions::new(settings.database().uri());
+    sea.sqlx_logging_level(log::LevelFilter::Debug);
+    let db = Arc::new(Database::connect(sea).await?);
+
+    Migrator::up(db.as_ref(), None).await?;
+
+    let mut indexers = Vec::new();
+
+    for (chain_id, chain) in settings.chains() {
+        if cli.chain.as_ref().is_some_and(|chain| chain != chain_id) {
+            continue;
+        }
+
+        let rpc = match (&cli.fixtures, chain.rpc()) {
+            (Some(fixtures), _) => {
+                rpc::Rpc::new(rpc::FixtureTransport::new(fixtures.join(chain_id)))
+            }
+            (None, Some(url)) => rpc::Rpc::new(rpc::HttpTransport::new(
+                url,
+                Duration::from_secs(settings.indexer().timeout()),
+            )?),
+            (None, None) => {
+                log::warn!("chain {} has no rpc endpoint, skipping", chain_id);
+                continue;
+            }
+        };
+
+        indexers.push(indexer::Indexer::new(
+            chain_id,
+            chain.clone(),
+            db.clone(),
+            id.clone(),
+            rpc,
+        ));
+    }
+
+    if indexers.is_empty() {
+        return Err(eyre::eyre!("no chain to index"));
+    }
+
+    if cli.once {
+        for indexer in &indexers {
+            indexer.sync().await?;
+        }
+
+        return Ok(());
+    }
+
+    let interval = Duration::from_millis(settings.indexer().interval());
+
+    let mut handles = Vec::new();
+    for indexer in indexers {
+        log::info!("indexing chain {}", indexer.chain_id());
+
+        handles.push(tokio::spawn(async move { indexer.run(interval).await }));
+    }
+
+    for handle in handles {
+        handle.await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use eyre::{eyre, Result};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use crate::types::{Block, Receipt};
+
+#[async_trait::async_trait]
+pub trait Transport: Send + Sync {
+    async fn request(&self, method: &str, params: Value) -> Result<Value>;
+}
+
+pub struct HttpTransport {
+    client: reqwest::Client,
+    url: String,
+    id: AtomicU64,
+}
+
+impl HttpTransport {
+    pub fn new(url: &str, timeout: Duration) -> Result<Self> {
+        let client = reqwest::Client::builder().timeout(timeout).build()?;
+
+        Ok(Self {
+            client,
+            url: url.to_owned(),
+            id: AtomicU64::new(1),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Transport for HttpTransport {
+    async fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": self.id.fetch_add(1, Ordering::Relaxed),
+            "method": method,
+            "params": params,
+        });
+
+        let mut response = self
+            .client
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Value>()
+            .await?;
+
+        if let Some(error) = response.get("error") {
+            return Err(eyre!("{} failed: {}", method, error));
+        }
+
+        Ok(response
+            .get_mut("result")
+            .map(Value::take)
+            .unwrap_or_default())
+    }
+}
+
+// FixtureTransport replays recorded responses, the result of a call is read from
+// `<root>/<method>/<params>.json` where params are joined by underscores
+pub struct FixtureTransport {
+    root: PathBuf,
+}
+
+impl FixtureTransport {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, method: &str, params: &Value) -> PathBuf {
+        let params = params
+            .as_array()
+            .map(|params| {
+                params
+                    .iter()
+                    .map(|param| match param {
+                        Value::String(param) => param.clone(),
+                        param => param.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("_")
+            })
+            .unwrap_or_default();
+
+        let params = if params.is_empty() {
+            "result".to_owned()
+        } else {
+            params
+        };
+
+        self.root.join(method).join(format!("{}.json", params))
+    }
+}
+
+#[async_trait::async_trait]
+impl Transport for FixtureTransport {
+    async fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let path = self.path(method, &params);
+
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            // a missing fixture behaves like a node that does not know the object
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Value::Null),
+            Err(err) => Err(eyre!("failed to read {}: {}", path.display(), err)),
+        }
+    }
+}
+
+pub struct Rpc {
+    transport: Box<dyn Transport>,
+}
+
+impl Rpc {
+    pub fn new(transport: impl Transport + 'static) -> Self {
+        Self {
+            transport: Box::new(transport),
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<Option<T>> {
+        match self.transport.request(method, params).await? {
+            Value::Null => Ok(None),
+            value => Ok(Some(serde_json::from_value(value)?)),
+        }
+    }
+
+    pub async fn block_number(&self) -> Result<u64> {
+        let number = self
+            .call::<String>("eth_blockNumber", json!([]))
+            .await?
+            .ok_or_else(|| eyre!("eth_blockNumber returned nothing"))?;
+
+        Ok(u64::from_str_radix(number.trim_start_matches("0x"), 16)?)
+    }
+
+    pub async fn block(&self, number: u64) -> Result<Option<Block>> {
+        self.call(
+            "eth_getBlockByNumber",
+            json!([format!("{:#x}", number), true]),
+        )
+        .await
+    }
+
+    pub async fn receipt(&self, hash: &str) -> Result<Option<Receipt>> {
+        self.call("eth_getTransactionReceipt", json!([hash])).await
+    }
+}
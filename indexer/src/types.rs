@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::primitives::Uint256;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Block {
+    pub number: Uint256,
+    pub hash: String,
+    pub parent_hash: String,
+    pub timestamp: Uint256,
+    #[serde(default)]
+    pub transactions: Vec<Transaction>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+    pub hash: String,
+    pub from: String,
+    pub to: Option<String>,
+    pub value: Uint256,
+    pub input: String,
+    pub transaction_index: Uint256,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Receipt {
+    pub status: Option<Uint256>,
+    pub gas_used: Uint256,
+    pub effective_gas_price: Option<Uint256>,
+}
+
+impl Receipt {
+    pub fn succeeded(&self) -> bool {
+        self.status
+            .as_ref()
+            .is_none_or(|status| status != &Uint256::default())
+    }
+}
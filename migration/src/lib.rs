@@ -7,6 +7,14 @@ mod m20231229_115937_create_contract_table;
 mod m20231229_115954_create_asset_table;
 mod m20240106_091416_create_block_table;
 mod m20240119_143933_create_locked_asset_table;
+mod m20261018_091500_create_webhook_table;
+mod m20261018_120000_alter_contract_table;
+mod m20261018_150000_alter_contract_verifying_key;
+mod m20261018_180000_alter_locked_asset_table;
+mod m20261018_210000_create_asset_change_table;
+mod m20261018_230000_alter_block_extrinsic_revision;
+mod m20261018_233000_create_webhook_cursor_table;
+mod m20261018_235000_alter_contract_minted;
 
 pub struct Migrator;
 
@@ -21,6 +29,14 @@ impl MigratorTrait for Migrator {
             Box::new(m20231229_115954_create_asset_table::Migration),
             Box::new(m20240106_091416_create_block_table::Migration),
             Box::new(m20240119_143933_create_locked_asset_table::Migration),
+            Box::new(m20261018_091500_create_webhook_table::Migration),
+            Box::new(m20261018_120000_alter_contract_table::Migration),
+            Box::new(m20261018_150000_alter_contract_verifying_key::Migration),
+            Box::new(m20261018_180000_alter_locked_asset_table::Migration),
+            Box::new(m20261018_210000_create_asset_change_table::Migration),
+            Box::new(m20261018_230000_alter_block_extrinsic_revision::Migration),
+            Box::new(m20261018_233000_create_webhook_cursor_table::Migration),
+            Box::new(m20261018_235000_alter_contract_minted::Migration),
         ]
     }
 }
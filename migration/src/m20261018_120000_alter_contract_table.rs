@@ -0,0 +1,105 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        conflicts(manager).await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Contract::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Contract::ExpiresAt).timestamp_with_time_zone(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_contract_chain_id_asset_id")
+                    .table(Contract::Table)
+                    .col(Contract::ChainId)
+                    .col(Contract::AssetId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_contract_chain_id_asset_id")
+                    .table(Contract::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Contract::Table)
+                    .drop_column(Contract::ExpiresAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+// conflicts refuses to create the unique index while several contracts share a
+// chain and asset id, each of them may hold balances so an operator has to merge them
+async fn conflicts(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    let db = manager.get_connection();
+    let builder = db.get_database_backend();
+
+    let rows = db
+        .query_all(
+            builder.build(
+                Query::select()
+                    .columns([Contract::ChainId, Contract::AssetId])
+                    .from(Contract::Table)
+                    .group_by_columns([Contract::ChainId, Contract::AssetId])
+                    .and_having(Expr::expr(Expr::col(Contract::Id).count()).gt(1))
+                    .order_by(Contract::ChainId, Order::Asc)
+                    .order_by(Contract::AssetId, Order::Asc),
+            ),
+        )
+        .await?;
+
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let mut pairs = Vec::new();
+    for row in rows {
+        pairs.push(format!(
+            "({}, {})",
+            row.try_get::<String>("", "chain_id")?,
+            row.try_get::<String>("", "asset_id")?
+        ));
+    }
+
+    Err(DbErr::Migration(format!(
+        "contracts share a chain and asset id, merge them before migrating: {}",
+        pairs.join(", ")
+    )))
+}
+
+#[derive(DeriveIden)]
+enum Contract {
+    Table,
+    Id,
+    ChainId,
+    AssetId,
+    ExpiresAt,
+}
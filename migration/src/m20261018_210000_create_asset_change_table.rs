@@ -0,0 +1,108 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AssetChange::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AssetChange::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AssetChange::ClassId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AssetChange::ContractId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AssetChange::ChainId).string().not_null())
+                    .col(ColumnDef::new(AssetChange::AssetId).string().not_null())
+                    .col(
+                        ColumnDef::new(AssetChange::ExtrinsicId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AssetChange::BlockNumber)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AssetChange::TxHash).string().not_null())
+                    .col(ColumnDef::new(AssetChange::Address).string().not_null())
+                    .col(ColumnDef::new(AssetChange::Value).string().not_null())
+                    .col(ColumnDef::new(AssetChange::CreatedAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_asset_change_contract_id_block_number")
+                    .table(AssetChange::Table)
+                    .col(AssetChange::ContractId)
+                    .col(AssetChange::BlockNumber)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_asset_change_address")
+                    .table(AssetChange::Table)
+                    .col(AssetChange::Address)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_asset_change_extrinsic_id")
+                    .table(AssetChange::Table)
+                    .col(AssetChange::ExtrinsicId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AssetChange::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AssetChange {
+    Table,
+    Id,
+    ClassId,
+    ContractId,
+    ChainId,
+    AssetId,
+    ExtrinsicId,
+    BlockNumber,
+    TxHash,
+    Address,
+    Value,
+    CreatedAt,
+}
@@ -0,0 +1,113 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Block::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Block::Revision)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Extrinsic::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Extrinsic::Revision)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_block_chain_id_revision")
+                    .table(Block::Table)
+                    .col(Block::ChainId)
+                    .col(Block::Revision)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_extrinsic_chain_id_revision")
+                    .table(Extrinsic::Table)
+                    .col(Extrinsic::ChainId)
+                    .col(Extrinsic::Revision)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_extrinsic_chain_id_revision")
+                    .table(Extrinsic::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_block_chain_id_revision")
+                    .table(Block::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Extrinsic::Table)
+                    .drop_column(Extrinsic::Revision)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Block::Table)
+                    .drop_column(Block::Revision)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Block {
+    Table,
+    ChainId,
+    Revision,
+}
+
+#[derive(DeriveIden)]
+enum Extrinsic {
+    Table,
+    ChainId,
+    Revision,
+}
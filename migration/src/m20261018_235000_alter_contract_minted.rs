@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use num_bigint::BigUint;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Contract::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Contract::Minted)
+                            .string()
+                            .not_null()
+                            .default("0x0"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        backfill(manager).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Contract::Table)
+                    .drop_column(Contract::Minted)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+// backfill totals the fungible mints applied so far, amounts are decimal or
+// 0x-prefixed hex as in the ordinal
+async fn backfill(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    let db = manager.get_connection();
+    let builder = db.get_database_backend();
+
+    let rows = db
+        .query_all(
+            builder.build(
+                Query::select()
+                    .columns([Extrinsic::ChainId, Extrinsic::AssetId, Extrinsic::Value])
+                    .from(Extrinsic::Table)
+                    .and_where(Expr::col(Extrinsic::Operation).eq("mint"))
+                    .and_where(Expr::col(Extrinsic::State).is_in(["confirmed", "finalized"]))
+                    .and_where(Expr::col(Extrinsic::Protocol).is_in(["erc20", "eos20"])),
+            ),
+        )
+        .await?;
+
+    let mut minted = HashMap::<(String, String), BigUint>::new();
+
+    for row in rows {
+        let chain_id = row.try_get::<String>("", "chain_id")?;
+        let asset_id = row.try_get::<String>("", "asset_id")?;
+        let value = row.try_get::<String>("", "value")?;
+
+        let amount = match value.strip_prefix("0x") {
+            Some(hex) => BigUint::parse_bytes(hex.as_bytes(), 16),
+            None => BigUint::parse_bytes(value.as_bytes(), 10),
+        };
+
+        *minted.entry((chain_id, asset_id)).or_default() += amount.unwrap_or_default();
+    }
+
+    for ((chain_id, asset_id), amount) in minted {
+        db.execute(
+            builder.build(
+                Query::update()
+                    .table(Contract::Table)
+                    .value(Contract::Minted, format!("{:#x}", amount))
+                    .and_where(Expr::col(Contract::ChainId).eq(chain_id))
+                    .and_where(Expr::col(Contract::AssetId).eq(asset_id)),
+            ),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[derive(DeriveIden)]
+enum Contract {
+    Table,
+    ChainId,
+    AssetId,
+    Minted,
+}
+
+#[derive(DeriveIden)]
+enum Extrinsic {
+    Table,
+    ChainId,
+    AssetId,
+    Protocol,
+    Operation,
+    State,
+    Value,
+}
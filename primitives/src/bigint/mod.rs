@@ -1,4 +1,4 @@
-use num_bigint::BigUint;
+use num_bigint::{BigUint, ParseBigIntError};
 pub use num_traits::{FromBytes, FromPrimitive, Num, One, Pow, ToBytes, ToPrimitive, Zero};
 
 mod add;
@@ -16,11 +16,11 @@ impl Uint256 {
         Self(BigUint::from_bytes_be(&[0xff; 32]))
     }
 
-    pub fn from_str_prefixed(s: &str) -> Result<Self, ()> {
+    pub fn from_str_prefixed(s: &str) -> Result<Self, ParseBigIntError> {
         if let Some(hex) = s.strip_prefix("0x") {
-            BigUint::from_str_radix(hex, 16).map(Self).map_err(|_| ())
+            BigUint::from_str_radix(hex, 16).map(Self)
         } else {
-            BigUint::from_str_radix(s, 10).map(Self).map_err(|_| ())
+            BigUint::from_str_radix(s, 10).map(Self)
         }
     }
 
@@ -17,6 +17,9 @@ pub struct Model {
     pub transaction_count: i64,
     pub extrinsic_count: i64,
     pub state: BlockState,
+    // revision is bumped by the indexer whenever the state changes, it is ordered
+    // within a chain
+    pub revision: i64,
     pub mined_at: Option<TimeDateTimeWithTimeZone>,
     pub finalized_at: Option<TimeDateTimeWithTimeZone>,
     pub created_at: Option<TimeDateTimeWithTimeZone>,
@@ -20,10 +20,14 @@ pub struct Model {
     pub identifier: Option<String>,
     pub max_supply: Option<String>,
     pub mint_limit: Option<String>,
+    pub minted: String,
     pub not_before: Option<i64>,
     pub tx_hash: Option<String>,
     pub state: ContractState,
     pub deployed_at: Option<TimeDateTimeWithTimeZone>,
+    pub expires_at: Option<TimeDateTimeWithTimeZone>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub verifying_key: Option<String>,
     pub created_at: Option<TimeDateTimeWithTimeZone>,
     pub updated_at: Option<TimeDateTimeWithTimeZone>,
 }
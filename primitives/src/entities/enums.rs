@@ -1,4 +1,6 @@
-use sea_orm::{DeriveActiveEnum, EnumIter};
+use std::str::FromStr;
+
+use sea_orm::{DeriveActiveEnum, EnumIter, Iterable as _};
 use serde::{Deserialize, Serialize};
 
 use crate::bigint::Uint256;
@@ -8,6 +10,7 @@ use crate::bigint::Uint256;
 pub enum AmountValue {
     U256(Uint256),
     U64(u64),
+    I64(i64),
     F64(f64),
 }
 
@@ -23,17 +26,36 @@ impl From<u64> for AmountValue {
     }
 }
 
+impl From<i64> for AmountValue {
+    fn from(value: i64) -> Self {
+        Self::I64(value)
+    }
+}
+
 impl From<f64> for AmountValue {
     fn from(value: f64) -> Self {
         Self::F64(value)
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    EnumIter,
+    DeriveActiveEnum,
+)]
 #[sea_orm(rs_type = "String", db_type = "String(None)")]
 #[serde(rename_all = "kebab-case")]
 pub enum BlockState {
     #[sea_orm(string_value = "pending")]
+    #[default]
     Pending,
     #[sea_orm(string_value = "indexing")]
     Indexing,
@@ -45,33 +67,35 @@ pub enum BlockState {
     Dropped,
 }
 
-impl Default for BlockState {
-    fn default() -> Self {
-        Self::Pending
-    }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum,
+)]
 #[sea_orm(rs_type = "String", db_type = "String(None)")]
 #[serde(rename_all = "kebab-case")]
 pub enum ClassType {
     #[sea_orm(string_value = "fungible")]
+    #[default]
     Fungible,
     #[sea_orm(string_value = "non-fungible")]
     NonFungible,
 }
 
-impl Default for ClassType {
-    fn default() -> Self {
-        Self::Fungible
+impl ClassType {
+    pub fn protocols(&self) -> Vec<ContractType> {
+        ContractType::iter()
+            .filter(|protocol| ClassType::from(*protocol) == *self)
+            .collect()
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum,
+)]
 #[sea_orm(rs_type = "String", db_type = "String(None)")]
 #[serde(rename_all = "kebab-case")]
 pub enum ContractState {
     #[sea_orm(string_value = "pending")]
+    #[default]
     Pending,
     #[sea_orm(string_value = "deploying")]
     Deploying,
@@ -79,17 +103,14 @@ pub enum ContractState {
     Deployed,
 }
 
-impl Default for ContractState {
-    fn default() -> Self {
-        Self::Pending
-    }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum,
+)]
 #[sea_orm(rs_type = "String", db_type = "String(None)")]
 #[serde(rename_all = "kebab-case")]
 pub enum ContractType {
     #[sea_orm(string_value = "erc20")]
+    #[default]
     Erc20,
     #[sea_orm(string_value = "erc721")]
     Erc721,
@@ -99,12 +120,6 @@ pub enum ContractType {
     Eos420,
 }
 
-impl Default for ContractType {
-    fn default() -> Self {
-        Self::Erc20
-    }
-}
-
 impl From<ContractType> for ClassType {
     fn from(val: ContractType) -> Self {
         match val {
@@ -114,11 +129,39 @@ impl From<ContractType> for ClassType {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(None)")]
+#[serde(rename_all = "kebab-case")]
+pub enum DeliveryState {
+    #[sea_orm(string_value = "pending")]
+    #[default]
+    Pending,
+    #[sea_orm(string_value = "delivered")]
+    Delivered,
+    #[sea_orm(string_value = "failed")]
+    Failed,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    EnumIter,
+    DeriveActiveEnum,
+)]
 #[sea_orm(rs_type = "String", db_type = "String(None)")]
 #[serde(rename_all = "kebab-case")]
 pub enum DropReason {
     #[sea_orm(string_value = "unknown")]
+    #[default]
     Unknown,
     #[sea_orm(string_value = "context_missing")]
     ContextMissing,
@@ -144,21 +187,20 @@ pub enum DropReason {
     BalanceInsufficient,
     #[sea_orm(string_value = "supply_exceeded")]
     SupplyExceeded,
+    #[sea_orm(string_value = "proof_invalid")]
+    ProofInvalid,
 }
 
-impl Default for DropReason {
-    fn default() -> Self {
-        Self::Unknown
-    }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum,
+)]
 #[sea_orm(rs_type = "String", db_type = "String(None)")]
 #[serde(rename_all = "kebab-case")]
 pub enum ExtrinsicOperation {
     #[sea_orm(string_value = "deploy")]
     Deploy,
     #[sea_orm(string_value = "mint")]
+    #[default]
     Mint,
     #[sea_orm(string_value = "transfer")]
     Transfer,
@@ -166,30 +208,23 @@ pub enum ExtrinsicOperation {
     Stake,
     #[sea_orm(string_value = "burn")]
     Burn,
+    #[sea_orm(string_value = "unlock")]
+    Unlock,
 }
 
-impl Default for ExtrinsicOperation {
-    fn default() -> Self {
-        Self::Mint
-    }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum,
+)]
 #[sea_orm(rs_type = "String", db_type = "String(None)")]
 #[serde(rename_all = "kebab-case")]
 pub enum LockReason {
     #[sea_orm(string_value = "rollup")]
     Rollup,
     #[sea_orm(string_value = "user")]
+    #[default]
     User,
 }
 
-impl Default for LockReason {
-    fn default() -> Self {
-        Self::User
-    }
-}
-
 #[derive(Clone, Debug, Hash, Serialize, Deserialize)]
 pub enum NumberOrHash {
     Number(i64),
@@ -213,3 +248,19 @@ impl From<&str> for NumberOrHash {
         Self::Hash(value.to_owned())
     }
 }
+
+impl FromStr for NumberOrHash {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(());
+        }
+
+        match s.parse::<i64>() {
+            Ok(number) if number >= 0 => Ok(Self::Number(number)),
+            Ok(_) => Err(()),
+            Err(_) => Ok(Self::Hash(s.to_owned())),
+        }
+    }
+}
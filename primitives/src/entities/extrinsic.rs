@@ -25,6 +25,9 @@ pub struct Model {
     pub context: Option<String>,
     pub state: BlockState,
     pub drop_reason: Option<DropReason>,
+    // revision is bumped by the indexer whenever the state changes, it is ordered
+    // within a chain
+    pub revision: i64,
     pub created_at: Option<TimeDateTimeWithTimeZone>,
     pub updated_at: Option<TimeDateTimeWithTimeZone>,
 }
@@ -10,6 +10,18 @@ impl EntityId for entities::asset::Model {
     }
 }
 
+impl EntityId for entities::asset_change::Model {
+    fn id(&self) -> i64 {
+        self.id
+    }
+}
+
+impl EntityId for entities::block::Model {
+    fn id(&self) -> i64 {
+        self.id
+    }
+}
+
 impl EntityId for entities::class::Model {
     fn id(&self) -> i64 {
         self.id
@@ -28,8 +40,26 @@ impl EntityId for entities::extrinsic::Model {
     }
 }
 
+impl EntityId for entities::locked_asset::Model {
+    fn id(&self) -> i64 {
+        self.id
+    }
+}
+
 impl EntityId for entities::transaction::Model {
     fn id(&self) -> i64 {
         self.id
     }
 }
+
+impl EntityId for entities::webhook::Model {
+    fn id(&self) -> i64 {
+        self.id
+    }
+}
+
+impl EntityId for entities::webhook_delivery::Model {
+    fn id(&self) -> i64 {
+        self.id
+    }
+}
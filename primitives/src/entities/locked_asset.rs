@@ -20,6 +20,7 @@ pub struct Model {
     pub value: String,
     pub lock_reason: LockReason,
     pub expires_at: i64,
+    pub released_by: Option<String>,
     pub created_at: Option<TimeDateTimeWithTimeZone>,
     pub updated_at: Option<TimeDateTimeWithTimeZone>,
 }
@@ -3,18 +3,22 @@
 pub mod prelude;
 
 pub mod asset;
+pub mod asset_change;
 pub mod block;
 pub mod class;
 pub mod contract;
 pub mod extrinsic;
 pub mod locked_asset;
 pub mod transaction;
+pub mod webhook;
+pub mod webhook_cursor;
+pub mod webhook_delivery;
 
 mod enums;
 mod id;
 
 pub use enums::{
-    AmountValue, BlockState, ClassType, ContractState, ContractType, DropReason,
+    AmountValue, BlockState, ClassType, ContractState, ContractType, DeliveryState, DropReason,
     ExtrinsicOperation, LockReason, NumberOrHash,
 };
 pub use id::EntityId;
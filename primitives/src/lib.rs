@@ -13,6 +13,8 @@ pub mod v1;
 pub use bigint::Uint256;
 pub use cli::Cli;
 pub use ordinal::Ordinal;
-pub use pagination::{PaginationRequest, PaginationResponse};
-pub use setting::Setting;
+pub use pagination::{
+    PaginationCursor, PaginationRequest, PaginationResponse, PaginationResponseBuilder,
+};
+pub use setting::{ChainSetting, IndexerSetting, Setting, WebhookSetting};
 pub use status::{DataResponse, ErrorResponse};
@@ -2,7 +2,14 @@ use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 use serde_with::{formats::Lowercase, hex::Hex, serde_as, skip_serializing_none};
 
-use crate::entities::{ContractType, ExtrinsicOperation};
+use crate::{
+    bigint::Zero as _,
+    entities::{ContractType, DropReason, ExtrinsicOperation},
+    Uint256,
+};
+
+const TICK_MAX_LENGTH: usize = 32;
+const BATCH_MAX_LENGTH: usize = 64;
 
 #[skip_serializing_none]
 #[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
@@ -34,6 +41,233 @@ impl Ordinal {
     pub fn builder() -> OrdinalBuilder {
         OrdinalBuilder::default()
     }
+
+    // parse accepts the calldata either as 0x-prefixed hex or as the decoded data uri
+    pub fn parse(calldata: &str) -> Result<Self, DropReason> {
+        let calldata = calldata.trim();
+        if calldata.is_empty() || calldata == "0x" {
+            return Err(DropReason::ContextMissing);
+        }
+
+        let text = match calldata.strip_prefix("0x") {
+            Some(hex) => {
+                let bytes = hex::decode(hex).map_err(|_| DropReason::ContextMalformed)?;
+                String::from_utf8(bytes).map_err(|_| DropReason::ContextMalformed)?
+            }
+            None => calldata.to_owned(),
+        };
+
+        let payload = match text.strip_prefix("data:") {
+            Some(uri) => match uri.split_once(',') {
+                Some((media_type, payload)) if json(media_type) => payload,
+                _ => return Err(DropReason::ContextMalformed),
+            },
+            None => return Err(DropReason::ContextMalformed),
+        };
+
+        let value = serde_json::from_str::<serde_json::Value>(payload)
+            .map_err(|_| DropReason::ContextMalformed)?;
+
+        // protocol and operation are checked first so that they are reported precisely
+        match value.get("p").map(ContractType::deserialize) {
+            Some(Ok(_)) => (),
+            Some(Err(_)) => return Err(DropReason::ProtocolMismatch),
+            None => return Err(DropReason::ContextMalformed),
+        }
+
+        match value.get("op").map(ExtrinsicOperation::deserialize) {
+            Some(Ok(_)) => (),
+            Some(Err(_)) => return Err(DropReason::OperationUnsupported),
+            None => return Err(DropReason::ContextMalformed),
+        }
+
+        serde_json::from_value::<Ordinal>(value).map_err(|_| DropReason::ContextMalformed)
+    }
+
+    pub fn validate(&self) -> Result<(), DropReason> {
+        match self.protocol {
+            ContractType::Eos20 | ContractType::Eos420 => (),
+            ContractType::Erc20 | ContractType::Erc721 => return Err(DropReason::ProtocolMismatch),
+        }
+
+        if self.tick.is_empty()
+            || self.tick.len() > TICK_MAX_LENGTH
+            || !self
+                .tick
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(DropReason::OperationInvalid);
+        }
+
+        let fungible = self.protocol == ContractType::Eos20;
+
+        match self.operation {
+            ExtrinsicOperation::Deploy => {
+                if self.operands().is_some() {
+                    return Err(DropReason::OperationInvalid);
+                }
+
+                let max_supply = positive(self.max_supply.as_deref())?;
+
+                if let Some(mint_limit) = &self.mint_limit {
+                    if positive(Some(mint_limit))? > max_supply {
+                        return Err(DropReason::OperationInvalid);
+                    }
+                }
+
+                integer(self.not_before.as_deref())?;
+            }
+            ExtrinsicOperation::Mint => {
+                let operand = self.single()?;
+
+                if operand.to.is_some() {
+                    return Err(DropReason::OperationInvalid);
+                }
+
+                if fungible {
+                    positive(operand.amount.as_deref())?;
+                } else if operand.amount.is_some() {
+                    return Err(DropReason::OperationInvalid);
+                }
+            }
+            ExtrinsicOperation::Transfer => {
+                let operands = match &self.operand {
+                    Some(SingleOrBatch::Batch(operands)) => {
+                        if operands.is_empty() || operands.len() > BATCH_MAX_LENGTH {
+                            return Err(DropReason::OperationInvalid);
+                        }
+
+                        operands.iter().collect()
+                    }
+                    _ => vec![self.single()?],
+                };
+
+                for operand in operands {
+                    if operand.to.as_deref().unwrap_or_default().is_empty() {
+                        return Err(DropReason::OperationInvalid);
+                    }
+
+                    asset(operand, fungible)?;
+                }
+            }
+            ExtrinsicOperation::Burn => {
+                let operand = self.single()?;
+
+                if operand.to.is_some() {
+                    return Err(DropReason::OperationInvalid);
+                }
+
+                asset(operand, fungible)?;
+            }
+            ExtrinsicOperation::Stake => {
+                let operand = self.single()?;
+
+                asset(operand, fungible)?;
+
+                if self.period.is_none() && self.expires_at.is_none() {
+                    return Err(DropReason::OperationInvalid);
+                }
+
+                integer(self.period.as_deref())?;
+                integer(self.expires_at.as_deref())?;
+            }
+            ExtrinsicOperation::Unlock => {
+                let operand = self.single()?;
+
+                // to names the owner of the lock, fungible unlocks release every lock
+                if operand.to.as_deref().unwrap_or_default().is_empty() {
+                    return Err(DropReason::OperationInvalid);
+                }
+
+                if fungible {
+                    if operand.amount.is_some() || operand.id.is_some() {
+                        return Err(DropReason::OperationInvalid);
+                    }
+                } else {
+                    asset(operand, fungible)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn encode(&self) -> String {
+        format!("data:,{}", serde_json::to_string(self).unwrap_or_default())
+    }
+
+    pub fn calldata(&self) -> String {
+        format!("0x{}", hex::encode(self.encode()))
+    }
+
+    // operands ignores the empty single operand that flattening always produces
+    pub fn operands(&self) -> Option<Vec<&Operand>> {
+        match &self.operand {
+            Some(SingleOrBatch::Batch(operands)) => Some(operands.iter().collect()),
+            Some(SingleOrBatch::Single(operand)) if !operand.is_empty() => Some(vec![operand]),
+            _ => None,
+        }
+    }
+
+    fn single(&self) -> Result<&Operand, DropReason> {
+        match &self.operand {
+            Some(SingleOrBatch::Single(operand)) => Ok(operand),
+            _ => Err(DropReason::OperationInvalid),
+        }
+    }
+}
+
+// json tells whether a data uri media type carries plain json, parameters such as
+// charset are allowed but base64 payloads are not
+fn json(media_type: &str) -> bool {
+    let mut parts = media_type.split(';').map(str::trim);
+
+    let essence = parts.next().unwrap_or_default();
+    if !(essence.is_empty() || essence.eq_ignore_ascii_case("application/json")) {
+        return false;
+    }
+
+    parts.all(
+        |parameter| matches!(parameter.split_once('='), Some((name, _)) if !name.trim().is_empty()),
+    )
+}
+
+fn positive(value: Option<&str>) -> Result<Uint256, DropReason> {
+    let value = value
+        .and_then(|value| Uint256::from_str_prefixed(value).ok())
+        .ok_or(DropReason::OperationInvalid)?;
+
+    if value.is_zero() {
+        return Err(DropReason::OperationInvalid);
+    }
+
+    Ok(value)
+}
+
+fn integer(value: Option<&str>) -> Result<(), DropReason> {
+    match value.map(str::parse::<i64>) {
+        Some(Ok(value)) if value >= 0 => Ok(()),
+        Some(_) => Err(DropReason::OperationInvalid),
+        None => Ok(()),
+    }
+}
+
+// asset checks that an operand names either an amount or a token id, as the protocol expects
+fn asset(operand: &Operand, fungible: bool) -> Result<(), DropReason> {
+    if fungible {
+        if operand.id.is_some() {
+            return Err(DropReason::OperationInvalid);
+        }
+
+        positive(operand.amount.as_deref())?;
+    } else {
+        if operand.amount.is_some() || operand.id.as_deref().unwrap_or_default().is_empty() {
+            return Err(DropReason::OperationInvalid);
+        }
+    }
+
+    Ok(())
 }
 
 #[skip_serializing_none]
@@ -45,6 +279,12 @@ pub struct Operand {
     pub to: Option<String>,
 }
 
+impl Operand {
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.amount.is_none() && self.to.is_none()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum SingleOrBatch {
     #[serde(rename = "to")]
@@ -53,8 +293,9 @@ pub enum SingleOrBatch {
     Single(Operand),
 }
 
+// A holds the two public inputs of the proof as big-endian field elements
 #[serde_as]
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct A(
     #[serde_as(as = "Hex<Lowercase>")] pub Vec<u8>,
     #[serde_as(as = "Hex<Lowercase>")] pub Vec<u8>,
@@ -67,3 +308,340 @@ pub struct Proof(
     #[serde_as(as = "[[Hex<Lowercase>; 2]; 2]")] pub [[Vec<u8>; 2]; 2],
     #[serde_as(as = "[Hex<Lowercase>; 2]")] pub [Vec<u8>; 2],
 );
+
+// VerifyingKey is a Groth16 key in the same encoding as Proof, G2 coordinates are
+// ordered imaginary part first as expected by the EVM pairing precompile
+#[serde_as]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct VerifyingKey {
+    #[serde_as(as = "[Hex<Lowercase>; 2]")]
+    pub alpha: [Vec<u8>; 2],
+    #[serde_as(as = "[[Hex<Lowercase>; 2]; 2]")]
+    pub beta: [[Vec<u8>; 2]; 2],
+    #[serde_as(as = "[[Hex<Lowercase>; 2]; 2]")]
+    pub gamma: [[Vec<u8>; 2]; 2],
+    #[serde_as(as = "[[Hex<Lowercase>; 2]; 2]")]
+    pub delta: [[Vec<u8>; 2]; 2],
+    #[serde_as(as = "Vec<[Hex<Lowercase>; 2]>")]
+    pub ic: Vec<[Vec<u8>; 2]>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> Result<Ordinal, DropReason> {
+        Ordinal::parse(&format!("data:,{}", json))
+    }
+
+    fn validate(json: &str) -> Result<(), DropReason> {
+        parse(json)?.validate()
+    }
+
+    #[test]
+    fn parses_hex_and_data_uri() {
+        let text = r#"data:,{"p":"eos20","op":"mint","tick":"abc","amt":"10"}"#;
+
+        for calldata in [
+            text.to_owned(),
+            format!("0x{}", hex::encode(text)),
+            format!("  {}\n", text),
+        ] {
+            let ordinal = Ordinal::parse(&calldata).unwrap();
+
+            assert_eq!(ordinal.protocol, ContractType::Eos20);
+            assert_eq!(ordinal.operation, ExtrinsicOperation::Mint);
+            assert_eq!(ordinal.tick, "abc");
+            assert_eq!(ordinal.single().unwrap().amount.as_deref(), Some("10"));
+        }
+
+        let ordinal = Ordinal::parse(text).unwrap();
+        assert_eq!(Ordinal::parse(&ordinal.calldata()).unwrap().tick, "abc");
+    }
+
+    #[test]
+    fn accepts_json_media_types() {
+        let json = r#"{"p":"eos20","op":"mint","tick":"abc","amt":"10"}"#;
+
+        for media_type in [
+            "",
+            "application/json",
+            "APPLICATION/JSON",
+            "application/json;charset=utf-8",
+            "application/json; charset=UTF-8",
+            ";charset=utf-8",
+        ] {
+            assert!(
+                Ordinal::parse(&format!("data:{},{}", media_type, json)).is_ok(),
+                "{} is json",
+                media_type
+            );
+        }
+
+        for media_type in [
+            "text/plain",
+            "application/json;base64",
+            "application/jsonx",
+            "application/json;=utf-8",
+        ] {
+            assert_eq!(
+                Ordinal::parse(&format!("data:{},{}", media_type, json)).unwrap_err(),
+                DropReason::ContextMalformed,
+                "{} is not json",
+                media_type
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_missing_context() {
+        for calldata in ["", "  ", "0x"] {
+            assert_eq!(
+                Ordinal::parse(calldata).unwrap_err(),
+                DropReason::ContextMissing
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_context() {
+        for calldata in [
+            "0xzz".to_owned(),
+            "0x123".to_owned(),
+            // invalid utf-8
+            "0xc328".to_owned(),
+            format!("0x{}ff", hex::encode("data:,")),
+            r#"{"p":"eos20","op":"mint","tick":"abc"}"#.to_owned(),
+            "data:".to_owned(),
+            "data:,{".to_owned(),
+            r#"data:,{"op":"mint","tick":"abc"}"#.to_owned(),
+            r#"data:,{"p":"eos20","tick":"abc"}"#.to_owned(),
+            r#"data:,{"p":"eos20","op":"mint"}"#.to_owned(),
+        ] {
+            assert_eq!(
+                Ordinal::parse(&calldata).unwrap_err(),
+                DropReason::ContextMalformed,
+                "{}",
+                calldata
+            );
+        }
+    }
+
+    #[test]
+    fn reports_protocol_and_operation() {
+        assert_eq!(
+            parse(r#"{"p":"brc20","op":"swap","tick":"abc"}"#).unwrap_err(),
+            DropReason::ProtocolMismatch
+        );
+        assert_eq!(
+            parse(r#"{"p":"eos20","op":"swap","tick":"abc"}"#).unwrap_err(),
+            DropReason::OperationUnsupported
+        );
+
+        for protocol in ["erc20", "erc721"] {
+            assert_eq!(
+                validate(&format!(
+                    r#"{{"p":"{}","op":"mint","tick":"abc","amt":"1"}}"#,
+                    protocol
+                ))
+                .unwrap_err(),
+                DropReason::ProtocolMismatch
+            );
+        }
+    }
+
+    #[test]
+    fn validates_ticks() {
+        assert!(validate(r#"{"p":"eos20","op":"mint","tick":"a-b_C9","amt":"1"}"#).is_ok());
+
+        for tick in ["", "a b", "ab$", &"a".repeat(TICK_MAX_LENGTH + 1)] {
+            assert_eq!(
+                validate(&format!(
+                    r#"{{"p":"eos20","op":"mint","tick":"{}","amt":"1"}}"#,
+                    tick
+                ))
+                .unwrap_err(),
+                DropReason::OperationInvalid,
+                "{}",
+                tick
+            );
+        }
+    }
+
+    #[test]
+    fn validates_deploy() {
+        assert!(validate(
+            r#"{"p":"eos20","op":"deploy","tick":"abc","max":"100","lim":"10","nbf":"5"}"#
+        )
+        .is_ok());
+        assert!(validate(r#"{"p":"eos420","op":"deploy","tick":"abc","max":"0x64"}"#).is_ok());
+
+        for json in [
+            r#"{"p":"eos20","op":"deploy","tick":"abc"}"#,
+            r#"{"p":"eos20","op":"deploy","tick":"abc","max":"0"}"#,
+            r#"{"p":"eos20","op":"deploy","tick":"abc","max":"ten"}"#,
+            r#"{"p":"eos20","op":"deploy","tick":"abc","max":"10","lim":"11"}"#,
+            r#"{"p":"eos20","op":"deploy","tick":"abc","max":"10","lim":"0"}"#,
+            r#"{"p":"eos20","op":"deploy","tick":"abc","max":"10","nbf":"-1"}"#,
+            r#"{"p":"eos20","op":"deploy","tick":"abc","max":"10","amt":"1"}"#,
+        ] {
+            assert_eq!(
+                validate(json).unwrap_err(),
+                DropReason::OperationInvalid,
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn validates_mint() {
+        assert!(validate(r#"{"p":"eos20","op":"mint","tick":"abc","amt":"1"}"#).is_ok());
+        assert!(validate(r#"{"p":"eos420","op":"mint","tick":"abc"}"#).is_ok());
+
+        for json in [
+            r#"{"p":"eos20","op":"mint","tick":"abc"}"#,
+            r#"{"p":"eos20","op":"mint","tick":"abc","amt":"0"}"#,
+            r#"{"p":"eos20","op":"mint","tick":"abc","amt":"1","to":"0xa"}"#,
+            r#"{"p":"eos420","op":"mint","tick":"abc","amt":"1"}"#,
+            r#"{"p":"eos20","op":"mint","tick":"abc","to":[{"amt":"1","to":"0xa"}]}"#,
+        ] {
+            assert_eq!(
+                validate(json).unwrap_err(),
+                DropReason::OperationInvalid,
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn validates_transfer() {
+        assert!(
+            validate(r#"{"p":"eos20","op":"transfer","tick":"abc","amt":"1","to":"0xa"}"#).is_ok()
+        );
+        assert!(
+            validate(r#"{"p":"eos420","op":"transfer","tick":"abc","id":"7","to":"0xa"}"#).is_ok()
+        );
+
+        for json in [
+            r#"{"p":"eos20","op":"transfer","tick":"abc","amt":"1"}"#,
+            r#"{"p":"eos20","op":"transfer","tick":"abc","amt":"1","to":""}"#,
+            r#"{"p":"eos20","op":"transfer","tick":"abc","id":"1","to":"0xa"}"#,
+            r#"{"p":"eos420","op":"transfer","tick":"abc","amt":"1","to":"0xa"}"#,
+            r#"{"p":"eos420","op":"transfer","tick":"abc","to":"0xa"}"#,
+        ] {
+            assert_eq!(
+                validate(json).unwrap_err(),
+                DropReason::OperationInvalid,
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn validates_batch_operands() {
+        let ordinal = parse(
+            r#"{"p":"eos20","op":"transfer","tick":"abc","to":[{"amt":"1","to":"0xa"},{"amt":"2","to":"0xb"}]}"#,
+        )
+        .unwrap();
+
+        assert!(ordinal.validate().is_ok());
+
+        let operands = ordinal.operands().unwrap();
+        assert_eq!(operands.len(), 2);
+        assert_eq!(operands[1].amount.as_deref(), Some("2"));
+        assert_eq!(operands[1].to.as_deref(), Some("0xb"));
+
+        let batch = |length: usize| {
+            format!(
+                r#"{{"p":"eos420","op":"transfer","tick":"abc","to":[{}]}}"#,
+                vec![r#"{"id":"1","to":"0xa"}"#; length].join(",")
+            )
+        };
+
+        assert!(validate(&batch(BATCH_MAX_LENGTH)).is_ok());
+
+        for json in [
+            batch(0),
+            batch(BATCH_MAX_LENGTH + 1),
+            r#"{"p":"eos20","op":"transfer","tick":"abc","to":[{"amt":"1","to":"0xa"},{"amt":"1"}]}"#.to_owned(),
+            r#"{"p":"eos20","op":"transfer","tick":"abc","to":[{"amt":"1","to":"0xa"},{"amt":"0","to":"0xb"}]}"#.to_owned(),
+            r#"{"p":"eos420","op":"transfer","tick":"abc","to":[{"amt":"1","to":"0xa"}]}"#.to_owned(),
+        ] {
+            assert_eq!(
+                validate(&json).unwrap_err(),
+                DropReason::OperationInvalid,
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn validates_burn() {
+        assert!(validate(r#"{"p":"eos20","op":"burn","tick":"abc","amt":"1"}"#).is_ok());
+        assert!(validate(r#"{"p":"eos420","op":"burn","tick":"abc","id":"7"}"#).is_ok());
+
+        for json in [
+            r#"{"p":"eos20","op":"burn","tick":"abc","amt":"1","to":"0xa"}"#,
+            r#"{"p":"eos20","op":"burn","tick":"abc","amt":"0"}"#,
+            r#"{"p":"eos420","op":"burn","tick":"abc"}"#,
+        ] {
+            assert_eq!(
+                validate(json).unwrap_err(),
+                DropReason::OperationInvalid,
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn validates_stake() {
+        assert!(
+            validate(r#"{"p":"eos20","op":"stake","tick":"abc","amt":"1","period":"60"}"#).is_ok()
+        );
+        assert!(
+            validate(r#"{"p":"eos420","op":"stake","tick":"abc","id":"7","exp":"100"}"#).is_ok()
+        );
+
+        for json in [
+            r#"{"p":"eos20","op":"stake","tick":"abc","amt":"1"}"#,
+            r#"{"p":"eos20","op":"stake","tick":"abc","amt":"1","period":"-1"}"#,
+            r#"{"p":"eos20","op":"stake","tick":"abc","amt":"1","exp":"soon"}"#,
+            r#"{"p":"eos20","op":"stake","tick":"abc","period":"60"}"#,
+            r#"{"p":"eos420","op":"stake","tick":"abc","amt":"1","period":"60"}"#,
+        ] {
+            assert_eq!(
+                validate(json).unwrap_err(),
+                DropReason::OperationInvalid,
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn validates_unlock() {
+        assert!(validate(r#"{"p":"eos20","op":"unlock","tick":"abc","to":"0xa"}"#).is_ok());
+        assert!(
+            validate(r#"{"p":"eos420","op":"unlock","tick":"abc","id":"7","to":"0xa"}"#).is_ok()
+        );
+
+        for json in [
+            r#"{"p":"eos20","op":"unlock","tick":"abc"}"#,
+            r#"{"p":"eos20","op":"unlock","tick":"abc","amt":"1","to":"0xa"}"#,
+            r#"{"p":"eos20","op":"unlock","tick":"abc","id":"7","to":"0xa"}"#,
+            r#"{"p":"eos420","op":"unlock","tick":"abc","to":"0xa"}"#,
+        ] {
+            assert_eq!(
+                validate(json).unwrap_err(),
+                DropReason::OperationInvalid,
+                "{}",
+                json
+            );
+        }
+    }
+}
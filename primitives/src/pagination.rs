@@ -1,6 +1,8 @@
+use std::{fmt, num::ParseIntError, str::FromStr};
+
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
-use serde_with::{serde_as, skip_serializing_none};
+use serde_with::{serde_as, skip_serializing_none, DisplayFromStr};
 
 #[serde_as]
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -13,6 +15,8 @@ where
     query: T,
     size: Option<u64>,
     page: Option<u64>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    cursor: Option<PaginationCursor>,
 }
 
 impl<T: Clone + Default> PaginationRequest<T> {
@@ -37,6 +41,10 @@ impl<T: Clone + Default> PaginationRequest<T> {
     pub fn page(&self) -> u64 {
         self.page.unwrap_or(0)
     }
+
+    pub fn cursor(&self) -> Option<PaginationCursor> {
+        self.cursor
+    }
 }
 
 #[serde_as]
@@ -51,7 +59,9 @@ where
     query: Option<T>,
     size: u64,
     page: u64,
-    total: u64,
+    total: Option<u64>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    next_cursor: Option<PaginationCursor>,
     #[builder(setter(each(name = "append", into)))]
     data: Vec<D>,
 }
@@ -61,3 +71,30 @@ impl<D: Clone + Default, T: Clone + Default> PaginationResponse<T, D> {
         PaginationResponseBuilder::<T, D>::default()
     }
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PaginationCursor(i64);
+
+impl PaginationCursor {
+    pub fn new(id: i64) -> Self {
+        Self(id)
+    }
+
+    pub fn id(&self) -> i64 {
+        self.0
+    }
+}
+
+impl fmt::Display for PaginationCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.0 as u64)
+    }
+}
+
+impl FromStr for PaginationCursor {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        u64::from_str_radix(s, 16).map(|id| Self(id as i64))
+    }
+}
@@ -1,10 +1,19 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::{entities::ContractType, Uint256};
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Setting {
     core: CoreSetting,
     database: DatabaseSetting,
+    stream: StreamSetting,
+    webhook: WebhookSetting,
+    deploy: DeploySetting,
+    chains: BTreeMap<String, ChainSetting>,
+    indexer: IndexerSetting,
 }
 
 impl Setting {
@@ -15,6 +24,30 @@ impl Setting {
     pub fn database(&self) -> &DatabaseSetting {
         &self.database
     }
+
+    pub fn stream(&self) -> &StreamSetting {
+        &self.stream
+    }
+
+    pub fn webhook(&self) -> &WebhookSetting {
+        &self.webhook
+    }
+
+    pub fn deploy(&self) -> &DeploySetting {
+        &self.deploy
+    }
+
+    pub fn chains(&self) -> &BTreeMap<String, ChainSetting> {
+        &self.chains
+    }
+
+    pub fn chain(&self, chain_id: &str) -> Option<&ChainSetting> {
+        self.chains.get(chain_id)
+    }
+
+    pub fn indexer(&self) -> &IndexerSetting {
+        &self.indexer
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -100,3 +133,211 @@ impl Default for DatabaseSetting {
         }
     }
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StreamSetting {
+    // interval is the polling interval for database changes in milliseconds
+    interval: u64,
+    capacity: usize,
+    keep_alive: u64,
+}
+
+impl StreamSetting {
+    pub fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn keep_alive(&self) -> u64 {
+        self.keep_alive
+    }
+}
+
+impl Default for StreamSetting {
+    fn default() -> Self {
+        Self {
+            interval: 1000,
+            capacity: 1024,
+            keep_alive: 15,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookSetting {
+    // interval is the polling interval for pending deliveries in milliseconds
+    interval: u64,
+    batch: u64,
+    timeout: u64,
+    backoff: u64,
+    max_backoff: u64,
+    max_attempts: i32,
+    // tokens are the bearer tokens allowed to register webhooks, registration is
+    // disabled without any
+    tokens: Vec<String>,
+}
+
+impl WebhookSetting {
+    pub fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    pub fn batch(&self) -> u64 {
+        self.batch
+    }
+
+    pub fn timeout(&self) -> u64 {
+        self.timeout
+    }
+
+    pub fn backoff(&self) -> u64 {
+        self.backoff
+    }
+
+    pub fn max_backoff(&self) -> u64 {
+        self.max_backoff
+    }
+
+    pub fn max_attempts(&self) -> i32 {
+        self.max_attempts
+    }
+
+    pub fn tokens(&self) -> &[String] {
+        &self.tokens
+    }
+}
+
+impl Default for WebhookSetting {
+    fn default() -> Self {
+        Self {
+            interval: 1000,
+            batch: 100,
+            timeout: 10,
+            backoff: 10,
+            max_backoff: 3600,
+            max_attempts: 8,
+            tokens: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeploySetting {
+    // expiry is how long a token name stays reserved awaiting its fee in seconds
+    expiry: u64,
+}
+
+impl DeploySetting {
+    pub fn expiry(&self) -> u64 {
+        self.expiry
+    }
+}
+
+impl Default for DeploySetting {
+    fn default() -> Self {
+        Self { expiry: 3600 }
+    }
+}
+
+// fee and fee_recipient have no default, a chain configured without them fails
+// to load instead of collecting deploy fees for someone else
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChainSetting {
+    #[serde(default)]
+    name: String,
+    #[serde(default = "ChainSetting::default_decimals")]
+    decimals: i32,
+    fee: Uint256,
+    fee_recipient: String,
+    // finality is the number of confirmations before a block is finalized
+    #[serde(default = "ChainSetting::default_finality")]
+    finality: u64,
+    #[serde(default = "ChainSetting::default_protocols")]
+    protocols: Vec<ContractType>,
+    // rpc is the JSON-RPC endpoint the indexer follows, chains without it are not indexed
+    #[serde(default)]
+    rpc: Option<String>,
+    #[serde(default)]
+    start_block: Option<u64>,
+}
+
+impl ChainSetting {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn decimals(&self) -> i32 {
+        self.decimals
+    }
+
+    pub fn fee(&self) -> &Uint256 {
+        &self.fee
+    }
+
+    pub fn fee_recipient(&self) -> &str {
+        &self.fee_recipient
+    }
+
+    pub fn finality(&self) -> u64 {
+        self.finality
+    }
+
+    pub fn protocols(&self) -> &[ContractType] {
+        &self.protocols
+    }
+
+    pub fn rpc(&self) -> Option<&str> {
+        self.rpc.as_deref()
+    }
+
+    pub fn start_block(&self) -> Option<u64> {
+        self.start_block
+    }
+}
+
+impl ChainSetting {
+    fn default_decimals() -> i32 {
+        18
+    }
+
+    fn default_finality() -> u64 {
+        12
+    }
+
+    fn default_protocols() -> Vec<ContractType> {
+        vec![ContractType::Eos20, ContractType::Eos420]
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IndexerSetting {
+    // interval is the polling interval for new blocks in milliseconds
+    interval: u64,
+    timeout: u64,
+}
+
+impl IndexerSetting {
+    pub fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    pub fn timeout(&self) -> u64 {
+        self.timeout
+    }
+}
+
+impl Default for IndexerSetting {
+    fn default() -> Self {
+        Self {
+            interval: 3000,
+            timeout: 30,
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::{
+    entities::{AmountValue, ClassType, ContractType, ExtrinsicOperation},
+    v1::{ContractResponse, ExtrinsicResponse},
+};
+
+with_prefix!(prefix_asset "asset_", &["chain_"]);
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AddressFindRequest {
+    pub chain_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActivityFindRequest {
+    pub chain_id: String,
+    pub asset_id: Option<Vec<String>>,
+    pub operation: Option<Vec<ExtrinsicOperation>>,
+    // since and until are unix timestamps in seconds
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
+#[builder(default, setter(into, strip_option, prefix = "with"))]
+pub struct PortfolioResponse {
+    chain_id: String,
+    address: String,
+    #[builder(setter(each(name = "append", into)))]
+    assets: Vec<PortfolioAssetResponse>,
+}
+
+impl PortfolioResponse {
+    pub fn builder() -> PortfolioResponseBuilder {
+        PortfolioResponseBuilder::default()
+    }
+
+    pub fn chain_id(&self) -> &str {
+        &self.chain_id
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn assets(&self) -> &[PortfolioAssetResponse] {
+        &self.assets
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
+#[builder(default, setter(into, strip_option, prefix = "with"))]
+pub struct PortfolioAssetResponse {
+    #[serde(flatten, with = "prefix_asset")]
+    contract: ContractResponse,
+    // amount is the aggregated fungible balance
+    amount: Option<AmountValue>,
+    // count is the number of non-fungible assets held
+    count: Option<u64>,
+    // locked is the locked fungible amount or the number of locked non-fungible assets
+    locked: Option<AmountValue>,
+    available: Option<AmountValue>,
+}
+
+impl PortfolioAssetResponse {
+    pub fn builder() -> PortfolioAssetResponseBuilder {
+        PortfolioAssetResponseBuilder::default()
+    }
+
+    pub fn contract(&self) -> &ContractResponse {
+        &self.contract
+    }
+
+    pub fn asset_id(&self) -> &str {
+        self.contract().id()
+    }
+
+    pub fn r#type(&self) -> ClassType {
+        self.contract().r#type()
+    }
+
+    pub fn protocol(&self) -> ContractType {
+        self.contract().protocol()
+    }
+
+    pub fn amount(&self) -> Option<&AmountValue> {
+        self.amount.as_ref()
+    }
+
+    pub fn count(&self) -> Option<u64> {
+        self.count
+    }
+
+    pub fn locked(&self) -> Option<&AmountValue> {
+        self.locked.as_ref()
+    }
+
+    pub fn available(&self) -> Option<&AmountValue> {
+        self.available.as_ref()
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
+#[builder(default, setter(into, strip_option, prefix = "with"))]
+pub struct ActivityResponse {
+    #[serde(flatten)]
+    extrinsic: ExtrinsicResponse,
+    direction: ActivityDirection,
+    // change is the signed balance change of the address, zero unless finalized
+    change: Option<AmountValue>,
+}
+
+impl ActivityResponse {
+    pub fn builder() -> ActivityResponseBuilder {
+        ActivityResponseBuilder::default()
+    }
+
+    pub fn extrinsic(&self) -> &ExtrinsicResponse {
+        &self.extrinsic
+    }
+
+    pub fn direction(&self) -> ActivityDirection {
+        self.direction
+    }
+
+    pub fn change(&self) -> Option<&AmountValue> {
+        self.change.as_ref()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ActivityDirection {
+    Sent,
+    #[default]
+    Received,
+    #[serde(rename = "self")]
+    SelfTransfer,
+    Mint,
+    Burn,
+    Stake,
+    Unlock,
+}
+
+impl ActivityDirection {
+    pub fn new(operation: ExtrinsicOperation, from: &str, to: &str, address: &str) -> Self {
+        match operation {
+            ExtrinsicOperation::Mint => Self::Mint,
+            ExtrinsicOperation::Burn => Self::Burn,
+            ExtrinsicOperation::Stake => Self::Stake,
+            ExtrinsicOperation::Unlock => Self::Unlock,
+            _ if from == address && to == address => Self::SelfTransfer,
+            _ if from == address => Self::Sent,
+            _ => Self::Received,
+        }
+    }
+
+    // sign tells how the balance of address moved, locks leave balances untouched
+    pub fn sign(&self, from: &str, to: &str, address: &str) -> i8 {
+        match self {
+            Self::Mint if to == address => 1,
+            Self::Burn if from == address => -1,
+            Self::Sent => -1,
+            Self::Received => 1,
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWNER: &str = "0xaa";
+    const OTHER: &str = "0xbb";
+
+    fn change(
+        operation: ExtrinsicOperation,
+        from: &str,
+        to: &str,
+        address: &str,
+    ) -> (ActivityDirection, i8) {
+        let direction = ActivityDirection::new(operation, from, to, address);
+        (direction, direction.sign(from, to, address))
+    }
+
+    #[test]
+    fn signs_transfers() {
+        use ExtrinsicOperation::Transfer;
+
+        assert_eq!(
+            change(Transfer, OWNER, OTHER, OWNER),
+            (ActivityDirection::Sent, -1)
+        );
+        assert_eq!(
+            change(Transfer, OWNER, OTHER, OTHER),
+            (ActivityDirection::Received, 1)
+        );
+        assert_eq!(
+            change(Transfer, OWNER, OWNER, OWNER),
+            (ActivityDirection::SelfTransfer, 0)
+        );
+    }
+
+    #[test]
+    fn signs_mints_and_burns() {
+        assert_eq!(
+            change(ExtrinsicOperation::Mint, OWNER, OWNER, OWNER),
+            (ActivityDirection::Mint, 1)
+        );
+        assert_eq!(
+            change(ExtrinsicOperation::Mint, OTHER, OWNER, OTHER),
+            (ActivityDirection::Mint, 0)
+        );
+        assert_eq!(
+            change(ExtrinsicOperation::Burn, OWNER, "", OWNER),
+            (ActivityDirection::Burn, -1)
+        );
+    }
+
+    #[test]
+    fn leaves_locks_unsigned() {
+        // staking and unlocking only record locks, no balance moves
+        for address in [OWNER, OTHER] {
+            assert_eq!(
+                change(ExtrinsicOperation::Stake, OWNER, OTHER, address),
+                (ActivityDirection::Stake, 0)
+            );
+            assert_eq!(
+                change(ExtrinsicOperation::Unlock, OWNER, OTHER, address),
+                (ActivityDirection::Unlock, 0)
+            );
+        }
+
+        assert_eq!(
+            change(ExtrinsicOperation::Stake, OWNER, OWNER, OWNER),
+            (ActivityDirection::Stake, 0)
+        );
+    }
+}
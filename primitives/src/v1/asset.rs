@@ -15,6 +15,8 @@ pub struct AssetFindRequest {
     pub chain_id: Option<String>,
     pub address: Option<String>,
     pub asset_id: Option<Vec<String>>,
+    // at_block answers holdings as of the given block height
+    pub at_block: Option<i64>,
 }
 
 #[skip_serializing_none]
@@ -29,6 +31,8 @@ pub struct AssetResponse {
     // identifier is hex string without 0x prefix or string
     identifier: Option<String>,
     locked: Option<BoolOrAmount>,
+    // available is the fungible balance not held by a lock
+    available: Option<AmountValue>,
 }
 
 impl AssetResponse {
@@ -75,6 +79,10 @@ impl AssetResponse {
     pub fn identifier(&self) -> Option<&str> {
         self.identifier.as_deref()
     }
+
+    pub fn available(&self) -> Option<AmountValue> {
+        self.available.clone()
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
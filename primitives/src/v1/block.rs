@@ -3,7 +3,14 @@ use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use time::{serde::rfc3339, OffsetDateTime};
 
-use crate::entities::BlockState;
+use crate::{entities::BlockState, v1::ExtrinsicResponse};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BlockFindRequest {
+    pub chain_id: Option<String>,
+    pub state: Option<Vec<BlockState>>,
+}
 
 #[skip_serializing_none]
 #[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
@@ -57,3 +64,36 @@ impl BlockResponse {
         self.finalized_at.as_ref()
     }
 }
+
+// ReorgResponse lists the extrinsics reverted with a block orphaned by a reorg
+#[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
+#[builder(default, setter(into, strip_option, prefix = "with"))]
+pub struct ReorgResponse {
+    chain_id: String,
+    number: i64,
+    hash: String,
+    #[builder(setter(each(name = "append", into)))]
+    extrinsics: Vec<ExtrinsicResponse>,
+}
+
+impl ReorgResponse {
+    pub fn builder() -> ReorgResponseBuilder {
+        ReorgResponseBuilder::default()
+    }
+
+    pub fn chain_id(&self) -> &str {
+        &self.chain_id
+    }
+
+    pub fn number(&self) -> i64 {
+        self.number
+    }
+
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    pub fn extrinsics(&self) -> &[ExtrinsicResponse] {
+        &self.extrinsics
+    }
+}
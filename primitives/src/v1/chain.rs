@@ -0,0 +1,32 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::entities::{AmountValue, ContractType};
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
+#[builder(default, setter(into, strip_option, prefix = "with"))]
+pub struct ChainResponse {
+    chain_id: String,
+    name: String,
+    decimals: i32,
+    fee: Option<AmountValue>,
+    fee_recipient: String,
+    finality: u64,
+    protocols: Vec<ContractType>,
+}
+
+impl ChainResponse {
+    pub fn builder() -> ChainResponseBuilder {
+        ChainResponseBuilder::default()
+    }
+
+    pub fn chain_id(&self) -> &str {
+        &self.chain_id
+    }
+
+    pub fn protocols(&self) -> &[ContractType] {
+        &self.protocols
+    }
+}
@@ -1,6 +1,6 @@
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
-use serde_with::skip_serializing_none;
+use serde_with::{serde_as, skip_serializing_none, DisplayFromStr};
 use time::{serde::rfc3339, OffsetDateTime};
 
 use crate::entities::{AmountValue, ClassType, ContractState, ContractType};
@@ -11,6 +11,8 @@ pub struct ContractFindRequest {
     pub chain_id: Option<String>,
     pub r#type: Option<Vec<ClassType>>,
     pub protocol: Option<Vec<ContractType>>,
+    // at_block answers holder queries as of the given block height
+    pub at_block: Option<i64>,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -20,8 +22,11 @@ pub struct ContractDeployRequest {
     pub chain_id: Option<String>,
     pub name: Option<String>,
     pub address: Option<String>,
+    // verifying_key is a JSON encoded Groth16 key required for proof carrying operations
+    pub verifying_key: Option<String>,
 }
 
+#[serde_as]
 #[skip_serializing_none]
 #[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
 #[builder(default, setter(into, strip_option, prefix = "with"))]
@@ -29,6 +34,8 @@ pub struct ContractResponse {
     chain_id: String,
     #[serde(skip_serializing_if = "String::is_empty")]
     id: String,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    deploy_id: Option<i64>,
     r#type: ClassType,
     protocol: ContractType,
     name: String,
@@ -42,6 +49,8 @@ pub struct ContractResponse {
     not_before: Option<i64>,
     #[serde(with = "rfc3339::option")]
     deployed_at: Option<OffsetDateTime>,
+    #[serde(with = "rfc3339::option")]
+    expires_at: Option<OffsetDateTime>,
     tx_hash: Option<String>,
     owner: Option<String>,
     to_address: Option<String>,
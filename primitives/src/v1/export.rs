@@ -0,0 +1,100 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{BlockState, ContractType, DropReason, ExtrinsicOperation};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Ndjson,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Ndjson => "ndjson",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportRequest<T>
+where
+    T: Clone + Default,
+{
+    #[serde(flatten)]
+    query: T,
+    format: ExportFormat,
+}
+
+impl<T: Clone + Default> ExportRequest<T> {
+    pub fn query(&self) -> &T {
+        &self.query
+    }
+
+    pub fn format(&self) -> ExportFormat {
+        self.format
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HolderExportRequest {
+    pub chain_id: Option<String>,
+    pub asset_id: String,
+    pub at_block: Option<i64>,
+}
+
+// export rows are flat and keep every column, so csv records stay aligned
+#[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
+#[builder(default, setter(into, strip_option, prefix = "with"))]
+pub struct ExtrinsicExportResponse {
+    chain_id: String,
+    block_number: Option<i64>,
+    block_hash: Option<String>,
+    tx_index: Option<i64>,
+    tx_hash: String,
+    index: i64,
+    asset_id: String,
+    protocol: ContractType,
+    from_address: String,
+    to_address: String,
+    operation: ExtrinsicOperation,
+    state: BlockState,
+    drop_reason: Option<DropReason>,
+    // amount is the exact decimal amount of fungible assets
+    amount: Option<String>,
+    identifier: Option<String>,
+}
+
+impl ExtrinsicExportResponse {
+    pub fn builder() -> ExtrinsicExportResponseBuilder {
+        ExtrinsicExportResponseBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
+#[builder(default, setter(into, strip_option, prefix = "with"))]
+pub struct HolderExportResponse {
+    address: String,
+    // amount is the exact decimal balance, or the token count for non-fungible assets
+    amount: String,
+    share: f64,
+}
+
+impl HolderExportResponse {
+    pub fn builder() -> HolderExportResponseBuilder {
+        HolderExportResponseBuilder::default()
+    }
+}
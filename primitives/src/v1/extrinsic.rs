@@ -4,7 +4,9 @@ use serde_with::skip_serializing_none;
 use time::OffsetDateTime;
 
 use crate::{
-    entities::{AmountValue, BlockState, ClassType, ContractType, DropReason, ExtrinsicOperation},
+    entities::{
+        self, AmountValue, BlockState, ClassType, ContractType, DropReason, ExtrinsicOperation,
+    },
     v1::{ContractResponse, TransactionResponse},
 };
 
@@ -16,11 +18,45 @@ with_prefix!(prefix_asset "asset_", &["chain_"]);
 pub struct ExtrinsicFindRequest {
     pub chain_id: String,
     pub block: Option<String>,
+    pub block_from: Option<i64>,
+    pub block_to: Option<i64>,
     pub tx_hash: Option<String>,
     pub asset_id: Option<Vec<String>>,
     pub address: Option<Vec<String>>,
 }
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExtrinsicStreamRequest {
+    pub chain_id: String,
+    pub asset_id: Option<Vec<String>>,
+    pub address: Option<Vec<String>>,
+}
+
+impl ExtrinsicStreamRequest {
+    pub fn matches(&self, extrinsic: &entities::extrinsic::Model) -> bool {
+        if extrinsic.chain_id != self.chain_id {
+            return false;
+        }
+
+        if let Some(assets) = &self.asset_id {
+            if !assets.contains(&extrinsic.asset_id) {
+                return false;
+            }
+        }
+
+        if let Some(addresses) = &self.address {
+            if !addresses.contains(&extrinsic.from_address)
+                && !addresses.contains(&extrinsic.to_address)
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
 #[skip_serializing_none]
 #[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
 #[builder(default, setter(into, strip_option, prefix = "with"))]
@@ -0,0 +1,39 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::entities::AmountValue;
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
+#[builder(default, setter(into, strip_option, prefix = "with"))]
+pub struct HolderResponse {
+    address: String,
+    // amount is the fungible balance held by the address
+    amount: Option<AmountValue>,
+    // count is the number of non-fungible assets held by the address
+    count: Option<u64>,
+    share: f64,
+}
+
+impl HolderResponse {
+    pub fn builder() -> HolderResponseBuilder {
+        HolderResponseBuilder::default()
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn amount(&self) -> Option<&AmountValue> {
+        self.amount.as_ref()
+    }
+
+    pub fn count(&self) -> Option<u64> {
+        self.count
+    }
+
+    pub fn share(&self) -> f64 {
+        self.share
+    }
+}
@@ -0,0 +1,68 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use time::{serde::rfc3339, OffsetDateTime};
+
+use crate::{
+    entities::{AmountValue, LockReason},
+    v1::ContractResponse,
+};
+
+with_prefix!(prefix_asset "asset_", &["chain_"]);
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LockFindRequest {
+    pub chain_id: Option<String>,
+    // address lists the locks an owner holds, delegate the locks it may release
+    pub address: Option<String>,
+    pub delegate: Option<String>,
+    pub asset_id: Option<Vec<String>>,
+    pub active: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
+#[builder(default, setter(into, strip_option, prefix = "with"))]
+pub struct LockResponse {
+    #[serde(flatten, with = "prefix_asset")]
+    contract: ContractResponse,
+    address: String,
+    delegate: String,
+    nonce: String,
+    // amount is 0x hex string or string
+    amount: Option<AmountValue>,
+    identifier: Option<String>,
+    lock_reason: LockReason,
+    // expires_at is absent for locks that never expire
+    #[serde(with = "rfc3339::option")]
+    expires_at: Option<OffsetDateTime>,
+    released: bool,
+    active: bool,
+}
+
+impl LockResponse {
+    pub fn builder() -> LockResponseBuilder {
+        LockResponseBuilder::default()
+    }
+
+    pub fn contract(&self) -> &ContractResponse {
+        &self.contract
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn delegate(&self) -> &str {
+        &self.delegate
+    }
+
+    pub fn expires_at(&self) -> Option<&OffsetDateTime> {
+        self.expires_at.as_ref()
+    }
+
+    pub fn active(&self) -> bool {
+        self.active
+    }
+}
@@ -1,13 +1,37 @@
+mod address;
 mod asset;
 mod block;
+mod chain;
 mod contract;
+mod export;
 mod extrinsic;
+mod holder;
+mod lock;
+mod ordinal;
 mod status;
+mod subscription;
 mod transaction;
+mod webhook;
 
+pub use address::{
+    ActivityDirection, ActivityFindRequest, ActivityResponse, AddressFindRequest,
+    PortfolioAssetResponse, PortfolioResponse,
+};
 pub use asset::{AssetFindRequest, AssetResponse};
-pub use block::BlockResponse;
+pub use block::{BlockFindRequest, BlockResponse, ReorgResponse};
+pub use chain::ChainResponse;
 pub use contract::{ContractDeployRequest, ContractFindRequest, ContractResponse};
-pub use extrinsic::{ExtrinsicFindRequest, ExtrinsicResponse};
-pub use status::{StatusRequest, StatusResponse};
-pub use transaction::{TransactionFindRequest, TransactionResponse};
+pub use export::{
+    ExportFormat, ExportRequest, ExtrinsicExportResponse, HolderExportRequest, HolderExportResponse,
+};
+pub use extrinsic::{ExtrinsicFindRequest, ExtrinsicResponse, ExtrinsicStreamRequest};
+pub use holder::HolderResponse;
+pub use lock::{LockFindRequest, LockResponse};
+pub use ordinal::{OrdinalEncodeRequest, OrdinalEncodeResponse, OrdinalOperandRequest};
+pub use status::{StatusRequest, StatusResponse, StatusResponseBuilder};
+pub use subscription::{SubscriptionMessage, SubscriptionRequest, Topic};
+pub use transaction::{TransactionDirection, TransactionFindRequest, TransactionResponse};
+pub use webhook::{
+    WebhookDeliveryFindRequest, WebhookDeliveryResponse, WebhookPayload, WebhookRequest,
+    WebhookResponse,
+};
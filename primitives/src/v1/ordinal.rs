@@ -0,0 +1,71 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::{
+    entities::{AmountValue, ContractType, ExtrinsicOperation},
+    ordinal::Operand,
+};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OrdinalOperandRequest {
+    pub id: Option<String>,
+    pub amount: Option<String>,
+    pub to: Option<String>,
+}
+
+impl From<OrdinalOperandRequest> for Operand {
+    fn from(value: OrdinalOperandRequest) -> Self {
+        Self {
+            id: value.id,
+            amount: value.amount,
+            to: value.to,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OrdinalEncodeRequest {
+    pub chain_id: Option<String>,
+    pub protocol: Option<ContractType>,
+    pub operation: Option<ExtrinsicOperation>,
+    pub tick: Option<String>,
+    pub name: Option<String>,
+    pub max_supply: Option<String>,
+    pub mint_limit: Option<String>,
+    pub uri: Option<String>,
+    pub not_before: Option<String>,
+    pub period: Option<String>,
+    pub expires_at: Option<String>,
+    #[serde(flatten)]
+    pub operand: OrdinalOperandRequest,
+    // batch holds the recipients of a batch transfer and replaces the single operand
+    pub batch: Option<Vec<OrdinalOperandRequest>>,
+}
+
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
+#[builder(default, setter(into, strip_option, prefix = "with"))]
+pub struct OrdinalEncodeResponse {
+    chain_id: String,
+    inscription: String,
+    calldata: String,
+    to_address: String,
+    fee: Option<AmountValue>,
+}
+
+impl OrdinalEncodeResponse {
+    pub fn builder() -> OrdinalEncodeResponseBuilder {
+        OrdinalEncodeResponseBuilder::default()
+    }
+
+    pub fn calldata(&self) -> &str {
+        &self.calldata
+    }
+
+    pub fn to_address(&self) -> &str {
+        &self.to_address
+    }
+}
@@ -1,6 +1,11 @@
+use std::collections::HashMap;
+
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
+use time::{serde::rfc3339, OffsetDateTime};
+
+use crate::entities::DropReason;
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[serde(default)]
@@ -16,10 +21,43 @@ pub struct StatusResponse {
     pending: u64,
     finalized: u64,
     dropped: u64,
+    drop_reasons: Option<HashMap<DropReason, u64>>,
+    assets: Option<HashMap<String, StatusResponse>>,
+    latest_block: Option<i64>,
+    // latest_block_at is when the latest block was indexed
+    #[serde(with = "rfc3339::option")]
+    latest_block_at: Option<OffsetDateTime>,
+    finalized_block: Option<i64>,
+    // lag is the number of blocks indexed but not yet finalized
+    lag: Option<i64>,
 }
 
 impl StatusResponse {
     pub fn builder() -> StatusResponseBuilder {
         StatusResponseBuilder::default()
     }
+
+    pub fn pending(&self) -> u64 {
+        self.pending
+    }
+
+    pub fn finalized(&self) -> u64 {
+        self.finalized
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    pub fn latest_block(&self) -> Option<i64> {
+        self.latest_block
+    }
+
+    pub fn finalized_block(&self) -> Option<i64> {
+        self.finalized_block
+    }
+
+    pub fn lag(&self) -> Option<i64> {
+        self.lag
+    }
 }
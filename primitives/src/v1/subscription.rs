@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use crate::v1::{ActivityResponse, BlockResponse, ExtrinsicResponse, ReorgResponse};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum Topic {
+    Block { chain_id: String },
+    Mint { chain_id: String, asset_id: String },
+    Balance { chain_id: String, address: String },
+}
+
+impl Topic {
+    pub fn chain_id(&self) -> &str {
+        match self {
+            Self::Block { chain_id }
+            | Self::Mint { chain_id, .. }
+            | Self::Balance { chain_id, .. } => chain_id,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "kebab-case")]
+pub enum SubscriptionRequest {
+    Subscribe { topic: Topic },
+    Unsubscribe { topic: Topic },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum SubscriptionMessage {
+    Subscribed {
+        topic: Topic,
+    },
+    Unsubscribed {
+        topic: Topic,
+    },
+    Block {
+        topic: Topic,
+        data: BlockResponse,
+    },
+    Reorg {
+        topic: Topic,
+        data: ReorgResponse,
+    },
+    Mint {
+        topic: Topic,
+        data: ExtrinsicResponse,
+    },
+    Balance {
+        topic: Topic,
+        data: ActivityResponse,
+    },
+    Error {
+        error_description: String,
+    },
+}
@@ -16,6 +16,16 @@ pub struct TransactionFindRequest {
     pub chain_id: String,
     pub block: Option<String>,
     pub address: Option<Vec<String>>,
+    pub direction: Option<TransactionDirection>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TransactionDirection {
+    In,
+    Out,
+    #[default]
+    Any,
 }
 
 #[skip_serializing_none]
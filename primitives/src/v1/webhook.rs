@@ -0,0 +1,122 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, skip_serializing_none, DisplayFromStr};
+use time::{serde::rfc3339, OffsetDateTime};
+
+use crate::{
+    entities::{BlockState, DeliveryState, ExtrinsicOperation},
+    v1::ExtrinsicResponse,
+};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookRequest {
+    pub chain_id: Option<String>,
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    pub asset_id: Option<String>,
+    pub address: Option<String>,
+    pub operation: Option<ExtrinsicOperation>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookDeliveryFindRequest {
+    pub state: Option<Vec<DeliveryState>>,
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
+#[builder(default, setter(into, strip_option, prefix = "with"))]
+pub struct WebhookResponse {
+    #[serde_as(as = "DisplayFromStr")]
+    id: i64,
+    chain_id: String,
+    url: String,
+    // secret is only revealed when the webhook is created or rotated
+    secret: Option<String>,
+    asset_id: Option<String>,
+    address: Option<String>,
+    operation: Option<ExtrinsicOperation>,
+    enabled: bool,
+    #[serde(with = "rfc3339::option")]
+    created_at: Option<OffsetDateTime>,
+}
+
+impl WebhookResponse {
+    pub fn builder() -> WebhookResponseBuilder {
+        WebhookResponseBuilder::default()
+    }
+
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn chain_id(&self) -> &str {
+        &self.chain_id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
+#[builder(default, setter(into, strip_option, prefix = "with"))]
+pub struct WebhookDeliveryResponse {
+    #[serde_as(as = "DisplayFromStr")]
+    id: i64,
+    event: BlockState,
+    state: DeliveryState,
+    attempts: i32,
+    response_status: Option<i32>,
+    error: Option<String>,
+    #[serde(with = "rfc3339::option")]
+    next_attempt_at: Option<OffsetDateTime>,
+    #[serde(with = "rfc3339::option")]
+    created_at: Option<OffsetDateTime>,
+    #[serde(with = "rfc3339::option")]
+    updated_at: Option<OffsetDateTime>,
+}
+
+impl WebhookDeliveryResponse {
+    pub fn builder() -> WebhookDeliveryResponseBuilder {
+        WebhookDeliveryResponseBuilder::default()
+    }
+
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn state(&self) -> DeliveryState {
+        self.state
+    }
+
+    pub fn attempts(&self) -> i32 {
+        self.attempts
+    }
+}
+
+#[serde_as]
+#[derive(Builder, Clone, Debug, Default, Serialize, Deserialize)]
+#[builder(default, setter(into, strip_option, prefix = "with"))]
+pub struct WebhookPayload {
+    #[serde_as(as = "DisplayFromStr")]
+    id: i64,
+    event: BlockState,
+    data: ExtrinsicResponse,
+}
+
+impl WebhookPayload {
+    pub fn builder() -> WebhookPayloadBuilder {
+        WebhookPayloadBuilder::default()
+    }
+}
@@ -0,0 +1,35 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{managers::WebhookManager, primitives::WebhookSetting};
+
+// dispatch enqueues deliveries for every chain from the persisted cursors, so
+// nothing is lost when the api restarts or falls behind
+pub async fn dispatch(
+    webhook_manager: Arc<WebhookManager>,
+    chains: Vec<String>,
+    setting: WebhookSetting,
+) {
+    let mut interval = tokio::time::interval(Duration::from_millis(setting.interval()));
+
+    loop {
+        interval.tick().await;
+
+        for chain_id in &chains {
+            if let Err(err) = webhook_manager.scan(chain_id).await {
+                log::warn!("failed to enqueue webhooks of {}: {}", chain_id, err);
+            }
+        }
+    }
+}
+
+// deliver sends pending deliveries, it runs apart from dispatch so that slow
+// endpoints only delay other deliveries
+pub async fn deliver(webhook_manager: Arc<WebhookManager>, setting: WebhookSetting) {
+    let mut interval = tokio::time::interval(Duration::from_millis(setting.interval()));
+
+    loop {
+        interval.tick().await;
+
+        webhook_manager.deliver(&setting).await;
+    }
+}
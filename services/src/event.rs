@@ -0,0 +1,30 @@
+use tokio::sync::broadcast;
+
+use crate::entities;
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    Block(entities::block::Model),
+    Extrinsic(entities::extrinsic::Model),
+    // Reorg carries an orphaned block with the extrinsics it reverted
+    Reorg(entities::block::Model, Vec<entities::extrinsic::Model>),
+}
+
+pub struct EventService(broadcast::Sender<Event>);
+
+impl EventService {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+
+        Self(sender)
+    }
+
+    pub fn publish(&self, event: Event) {
+        // no subscriber is not an error, the event is simply discarded
+        let _ = self.0.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.0.subscribe()
+    }
+}
@@ -0,0 +1,729 @@
+use std::collections::HashMap;
+
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait as _, ColumnTrait as _, ConnectionTrait, DbErr,
+    EntityTrait as _, PaginatorTrait as _, QueryFilter as _, QueryOrder as _, Set,
+};
+use time::OffsetDateTime;
+
+use crate::{
+    entities::{
+        self, BlockState, ClassType, ContractState, DropReason, ExtrinsicOperation, LockReason,
+    },
+    primitives::{
+        bigint::{FromPrimitive as _, Zero as _},
+        Ordinal, Uint256,
+    },
+    IdService,
+};
+
+// lock_nonce identifies the lock created by a stake extrinsic, and which unlock
+// released it
+pub fn lock_nonce(extrinsic: &entities::extrinsic::Model) -> String {
+    format!("{}:{}", extrinsic.tx_hash, extrinsic.index)
+}
+
+async fn contract<C: ConnectionTrait>(
+    db: &C,
+    extrinsic: &entities::extrinsic::Model,
+) -> Result<Option<entities::contract::Model>, DbErr> {
+    entities::contract::Entity::find()
+        .filter(entities::contract::Column::ChainId.eq(&extrinsic.chain_id))
+        .filter(entities::contract::Column::AssetId.eq(&extrinsic.asset_id))
+        .order_by_desc(entities::contract::Column::Id)
+        .one(db)
+        .await
+}
+
+async fn balance<C: ConnectionTrait>(
+    db: &C,
+    contract: &entities::contract::Model,
+    address: &str,
+) -> Result<Option<entities::asset::Model>, DbErr> {
+    entities::asset::Entity::find()
+        .filter(entities::asset::Column::ContractId.eq(contract.id))
+        .filter(entities::asset::Column::Address.eq(address))
+        .order_by_desc(entities::asset::Column::Id)
+        .one(db)
+        .await
+}
+
+async fn token<C: ConnectionTrait>(
+    db: &C,
+    contract: &entities::contract::Model,
+    identifier: &str,
+) -> Result<Option<entities::asset::Model>, DbErr> {
+    entities::asset::Entity::find()
+        .filter(entities::asset::Column::ContractId.eq(contract.id))
+        .filter(entities::asset::Column::Value.eq(identifier))
+        .order_by_desc(entities::asset::Column::Id)
+        .one(db)
+        .await
+}
+
+// record appends the state an extrinsic left an asset in to the history, fungible
+// records carry the balance of address and non-fungible ones the owner of the
+// token, empty once burned
+async fn record<C: ConnectionTrait>(
+    db: &C,
+    id: &IdService,
+    contract: &entities::contract::Model,
+    extrinsic: &entities::extrinsic::Model,
+    address: &str,
+    value: &str,
+) -> Result<(), DbErr> {
+    entities::asset_change::ActiveModel {
+        id: Set(id.next_id()),
+        class_id: Set(contract.class_id),
+        contract_id: Set(contract.id),
+        chain_id: Set(contract.chain_id.clone()),
+        asset_id: Set(contract.asset_id.clone()),
+        extrinsic_id: Set(extrinsic.id),
+        block_number: Set(extrinsic.block_number.unwrap_or_default()),
+        tx_hash: Set(extrinsic.tx_hash.clone()),
+        address: Set(address.to_owned()),
+        value: Set(value.to_owned()),
+        created_at: Set(Some(OffsetDateTime::now_utc())),
+    }
+    .insert(db)
+    .await?;
+
+    Ok(())
+}
+
+// credit adds amount to the fungible balance of address, the change is recorded
+// for the extrinsic that caused it and not when the ledger is rewound
+async fn credit<C: ConnectionTrait>(
+    db: &C,
+    id: &IdService,
+    contract: &entities::contract::Model,
+    address: &str,
+    amount: &Uint256,
+    change: Option<&entities::extrinsic::Model>,
+) -> Result<(), DbErr> {
+    let now = OffsetDateTime::now_utc();
+
+    let value = match balance(db, contract, address).await? {
+        Some(asset) => {
+            let value = Uint256::from_str_prefixed(&asset.value).unwrap_or_default() + amount;
+
+            let mut asset: entities::asset::ActiveModel = asset.into();
+            asset.value = Set(format!("{:#x}", value));
+            if let Some(extrinsic) = change {
+                asset.tx_hash = Set(Some(extrinsic.tx_hash.clone()));
+            }
+            asset.updated_at = Set(Some(now));
+            asset.update(db).await?;
+
+            value
+        }
+        None => {
+            entities::asset::ActiveModel {
+                id: Set(id.next_id()),
+                class_id: Set(contract.class_id),
+                contract_id: Set(contract.id),
+                chain_id: Set(contract.chain_id.clone()),
+                asset_id: Set(contract.asset_id.clone()),
+                tx_hash: Set(change.map(|extrinsic| extrinsic.tx_hash.clone())),
+                index: Set(None),
+                address: Set(address.to_owned()),
+                value: Set(format!("{:#x}", amount)),
+                created_at: Set(Some(now)),
+                updated_at: Set(Some(now)),
+            }
+            .insert(db)
+            .await?;
+
+            amount.clone()
+        }
+    };
+
+    if let Some(extrinsic) = change {
+        record(
+            db,
+            id,
+            contract,
+            extrinsic,
+            address,
+            &format!("{:#x}", value),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+// debit removes amount from the fungible balance of address, returns false
+// without touching the balance when it is insufficient
+async fn debit<C: ConnectionTrait>(
+    db: &C,
+    id: &IdService,
+    contract: &entities::contract::Model,
+    address: &str,
+    amount: &Uint256,
+    change: Option<&entities::extrinsic::Model>,
+) -> Result<bool, DbErr> {
+    let asset = match balance(db, contract, address).await? {
+        Some(asset) => asset,
+        None => return Ok(amount.is_zero()),
+    };
+
+    let value = Uint256::from_str_prefixed(&asset.value).unwrap_or_default();
+    if &value < amount {
+        return Ok(false);
+    }
+
+    let value = value - amount;
+
+    if value.is_zero() {
+        entities::asset::Entity::delete_by_id(asset.id)
+            .exec(db)
+            .await?;
+    } else {
+        let mut asset: entities::asset::ActiveModel = asset.into();
+        asset.value = Set(format!("{:#x}", value));
+        if let Some(extrinsic) = change {
+            asset.tx_hash = Set(Some(extrinsic.tx_hash.clone()));
+        }
+        asset.updated_at = Set(Some(OffsetDateTime::now_utc()));
+        asset.update(db).await?;
+    }
+
+    if let Some(extrinsic) = change {
+        record(
+            db,
+            id,
+            contract,
+            extrinsic,
+            address,
+            &format!("{:#x}", value),
+        )
+        .await?;
+    }
+
+    Ok(true)
+}
+
+// assign moves a non-fungible token to address, or burns it when there is none
+async fn assign<C: ConnectionTrait>(
+    db: &C,
+    id: &IdService,
+    contract: &entities::contract::Model,
+    identifier: &str,
+    address: Option<&str>,
+    change: Option<&entities::extrinsic::Model>,
+) -> Result<(), DbErr> {
+    let now = OffsetDateTime::now_utc();
+
+    match (token(db, contract, identifier).await?, address) {
+        (Some(asset), Some(address)) => {
+            let mut asset: entities::asset::ActiveModel = asset.into();
+            asset.address = Set(address.to_owned());
+            if let Some(extrinsic) = change {
+                asset.tx_hash = Set(Some(extrinsic.tx_hash.clone()));
+            }
+            asset.updated_at = Set(Some(now));
+            asset.update(db).await?;
+        }
+        (Some(asset), None) => {
+            entities::asset::Entity::delete_by_id(asset.id)
+                .exec(db)
+                .await?;
+        }
+        (None, Some(address)) => {
+            entities::asset::ActiveModel {
+                id: Set(id.next_id()),
+                class_id: Set(contract.class_id),
+                contract_id: Set(contract.id),
+                chain_id: Set(contract.chain_id.clone()),
+                asset_id: Set(contract.asset_id.clone()),
+                tx_hash: Set(change.map(|extrinsic| extrinsic.tx_hash.clone())),
+                index: Set(identifier.parse().ok()),
+                address: Set(address.to_owned()),
+                value: Set(identifier.to_owned()),
+                created_at: Set(Some(now)),
+                updated_at: Set(Some(now)),
+            }
+            .insert(db)
+            .await?;
+        }
+        (None, None) => (),
+    }
+
+    if let Some(extrinsic) = change {
+        record(
+            db,
+            id,
+            contract,
+            extrinsic,
+            address.unwrap_or_default(),
+            identifier,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+// locked sums the fungible amount address has locked at timestamp
+async fn locked<C: ConnectionTrait>(
+    db: &C,
+    contract: &entities::contract::Model,
+    address: &str,
+    timestamp: i64,
+) -> Result<Uint256, DbErr> {
+    let locks = entities::locked_asset::Entity::find()
+        .filter(entities::locked_asset::Column::ContractId.eq(contract.id))
+        .filter(entities::locked_asset::Column::Address.eq(address))
+        .filter(entities::locked_asset::Column::ExpiresAt.gt(timestamp))
+        .filter(entities::locked_asset::Column::ReleasedBy.is_null())
+        .all(db)
+        .await?;
+
+    Ok(locks.iter().fold(Uint256::zero(), |amount, lock| {
+        amount + Uint256::from_str_prefixed(&lock.value).unwrap_or_default()
+    }))
+}
+
+// owned tells whether address holds the non-fungible token unlocked at timestamp
+async fn owned<C: ConnectionTrait>(
+    db: &C,
+    contract: &entities::contract::Model,
+    identifier: &str,
+    address: &str,
+    timestamp: i64,
+) -> Result<bool, DbErr> {
+    match token(db, contract, identifier).await? {
+        Some(asset) if asset.address == address => (),
+        _ => return Ok(false),
+    }
+
+    let lock = entities::locked_asset::Entity::find()
+        .filter(entities::locked_asset::Column::ContractId.eq(contract.id))
+        .filter(entities::locked_asset::Column::Value.eq(identifier))
+        .filter(entities::locked_asset::Column::ExpiresAt.gt(timestamp))
+        .filter(entities::locked_asset::Column::ReleasedBy.is_null())
+        .one(db)
+        .await?;
+
+    Ok(lock.is_none())
+}
+
+// minted counts the tokens the applied mints of a non-fungible contract issued so
+// far, fungible contracts keep their running total on the contract
+async fn minted<C: ConnectionTrait>(
+    db: &C,
+    contract: &entities::contract::Model,
+) -> Result<u64, DbErr> {
+    entities::extrinsic::Entity::find()
+        .filter(entities::extrinsic::Column::ChainId.eq(&contract.chain_id))
+        .filter(entities::extrinsic::Column::AssetId.eq(&contract.asset_id))
+        .filter(entities::extrinsic::Column::Operation.eq(ExtrinsicOperation::Mint))
+        .filter(
+            entities::extrinsic::Column::State
+                .is_in([BlockState::Confirmed, BlockState::Finalized]),
+        )
+        .count(db)
+        .await
+}
+
+// issue stores the running minted total of a fungible contract
+async fn issue<C: ConnectionTrait>(
+    db: &C,
+    contract: &entities::contract::Model,
+    minted: Uint256,
+) -> Result<(), DbErr> {
+    let mut contract: entities::contract::ActiveModel = contract.clone().into();
+    contract.minted = Set(format!("{:#x}", minted));
+    contract.updated_at = Set(Some(OffsetDateTime::now_utc()));
+    contract.update(db).await?;
+
+    Ok(())
+}
+
+// expiry reads when the lock created by a stake extrinsic runs out, either an
+// absolute `exp` or a `period` from the block timestamp, locks without either
+// never expire
+fn expiry(extrinsic: &entities::extrinsic::Model, timestamp: i64) -> i64 {
+    let ordinal = match extrinsic.context.as_deref().map(Ordinal::parse) {
+        Some(Ok(ordinal)) => ordinal,
+        _ => return i64::MAX,
+    };
+
+    if let Some(expires_at) = ordinal.expires_at.and_then(|at| at.parse().ok()) {
+        return expires_at;
+    }
+
+    ordinal
+        .period
+        .and_then(|period| period.parse::<i64>().ok())
+        .map_or(i64::MAX, |period| timestamp.saturating_add(period))
+}
+
+// settle checks one extrinsic against the ledger and applies it, nothing is
+// written when it is rejected, issued counts the non-fungible mints settled before
+// it in the same batch per contract as they are not stored yet
+async fn settle<C: ConnectionTrait>(
+    db: &C,
+    id: &IdService,
+    extrinsic: &entities::extrinsic::Model,
+    timestamp: i64,
+    issued: &mut HashMap<i64, u64>,
+) -> Result<Result<(), DropReason>, DbErr> {
+    let contract = match contract(db, extrinsic).await? {
+        Some(contract)
+            if matches!(
+                contract.state,
+                ContractState::Deploying | ContractState::Deployed
+            ) =>
+        {
+            contract
+        }
+        _ => return Ok(Err(DropReason::OperationInvalid)),
+    };
+
+    if extrinsic.operation == ExtrinsicOperation::Unlock {
+        return unlock(db, &contract, extrinsic, timestamp).await;
+    }
+
+    // only the delegate releases a lock early, one that never expires and is
+    // delegated to the contract could never be released by anybody
+    if extrinsic.operation == ExtrinsicOperation::Stake
+        && expiry(extrinsic, timestamp) == i64::MAX
+        && extrinsic.to_address.eq_ignore_ascii_case(&contract.address)
+    {
+        return Ok(Err(DropReason::OperationInvalid));
+    }
+
+    let (from, to) = (&extrinsic.from_address, &extrinsic.to_address);
+
+    match ClassType::from(contract.protocol) {
+        ClassType::Fungible => {
+            let amount = match Uint256::from_str_prefixed(&extrinsic.value) {
+                Ok(amount) if !amount.is_zero() => amount,
+                _ => return Ok(Err(DropReason::OperationInvalid)),
+            };
+
+            if extrinsic.operation == ExtrinsicOperation::Mint {
+                let limit = contract
+                    .mint_limit
+                    .as_deref()
+                    .and_then(|limit| Uint256::from_str_prefixed(limit).ok());
+                if limit.is_some_and(|limit| amount > limit) {
+                    return Ok(Err(DropReason::OperationInvalid));
+                }
+
+                let supply = contract
+                    .max_supply
+                    .as_deref()
+                    .and_then(|supply| Uint256::from_str_prefixed(supply).ok());
+                let minted =
+                    Uint256::from_str_prefixed(&contract.minted).unwrap_or_default() + &amount;
+                if supply.is_some_and(|supply| minted > supply) {
+                    return Ok(Err(DropReason::SupplyExceeded));
+                }
+
+                credit(db, id, &contract, to, &amount, Some(extrinsic)).await?;
+                issue(db, &contract, minted).await?;
+
+                return Ok(Ok(()));
+            }
+
+            let balance = match balance(db, &contract, from).await? {
+                Some(asset) => Uint256::from_str_prefixed(&asset.value).unwrap_or_default(),
+                None => Uint256::zero(),
+            };
+            let locked = locked(db, &contract, from, timestamp).await?;
+
+            if balance < locked + &amount {
+                return Ok(Err(DropReason::BalanceInsufficient));
+            }
+
+            match extrinsic.operation {
+                ExtrinsicOperation::Transfer => {
+                    debit(db, id, &contract, from, &amount, Some(extrinsic)).await?;
+                    credit(db, id, &contract, to, &amount, Some(extrinsic)).await?;
+                }
+                ExtrinsicOperation::Burn => {
+                    debit(db, id, &contract, from, &amount, Some(extrinsic)).await?;
+                }
+                ExtrinsicOperation::Stake => {
+                    lock(
+                        db,
+                        id,
+                        &contract,
+                        extrinsic,
+                        &format!("{:#x}", amount),
+                        timestamp,
+                    )
+                    .await?;
+                }
+                _ => return Ok(Err(DropReason::OperationUnsupported)),
+            }
+        }
+        ClassType::NonFungible => {
+            let identifier = &extrinsic.value;
+
+            if extrinsic.operation == ExtrinsicOperation::Mint {
+                if token(db, &contract, identifier).await?.is_some() {
+                    return Ok(Err(DropReason::ExtrinsicConflicted));
+                }
+
+                let supply = contract
+                    .max_supply
+                    .as_deref()
+                    .and_then(|supply| Uint256::from_str_prefixed(supply).ok());
+                let pending = issued.entry(contract.id).or_default();
+                if let Some(supply) = supply {
+                    let minted = minted(db, &contract).await? + *pending + 1;
+                    if Uint256::from_u64(minted).unwrap_or_default() > supply {
+                        return Ok(Err(DropReason::SupplyExceeded));
+                    }
+                }
+
+                assign(db, id, &contract, identifier, Some(to), Some(extrinsic)).await?;
+                *pending += 1;
+
+                return Ok(Ok(()));
+            }
+
+            if !owned(db, &contract, identifier, from, timestamp).await? {
+                return Ok(Err(DropReason::OperationInvalid));
+            }
+
+            match extrinsic.operation {
+                ExtrinsicOperation::Transfer => {
+                    assign(db, id, &contract, identifier, Some(to), Some(extrinsic)).await?;
+                }
+                ExtrinsicOperation::Burn => {
+                    assign(db, id, &contract, identifier, None, Some(extrinsic)).await?;
+                }
+                ExtrinsicOperation::Stake => {
+                    lock(db, id, &contract, extrinsic, identifier, timestamp).await?;
+                }
+                _ => return Ok(Err(DropReason::OperationUnsupported)),
+            }
+        }
+    }
+
+    Ok(Ok(()))
+}
+
+// unlock releases the active locks of the owner the sender is delegate of, every
+// fungible lock or the lock on the named token
+async fn unlock<C: ConnectionTrait>(
+    db: &C,
+    contract: &entities::contract::Model,
+    extrinsic: &entities::extrinsic::Model,
+    timestamp: i64,
+) -> Result<Result<(), DropReason>, DbErr> {
+    let mut query = entities::locked_asset::Entity::update_many()
+        .col_expr(
+            entities::locked_asset::Column::ReleasedBy,
+            Expr::value(lock_nonce(extrinsic)),
+        )
+        .col_expr(
+            entities::locked_asset::Column::UpdatedAt,
+            Expr::value(OffsetDateTime::now_utc()),
+        )
+        .filter(entities::locked_asset::Column::ContractId.eq(contract.id))
+        .filter(entities::locked_asset::Column::Address.eq(&extrinsic.to_address))
+        .filter(entities::locked_asset::Column::Delegate.eq(&extrinsic.from_address))
+        .filter(entities::locked_asset::Column::ExpiresAt.gt(timestamp))
+        .filter(entities::locked_asset::Column::ReleasedBy.is_null());
+
+    if ClassType::from(contract.protocol) == ClassType::NonFungible {
+        query = query.filter(entities::locked_asset::Column::Value.eq(&extrinsic.value));
+    }
+
+    if query.exec(db).await?.rows_affected == 0 {
+        return Ok(Err(DropReason::OperationInvalid));
+    }
+
+    Ok(Ok(()))
+}
+
+async fn lock<C: ConnectionTrait>(
+    db: &C,
+    id: &IdService,
+    contract: &entities::contract::Model,
+    extrinsic: &entities::extrinsic::Model,
+    value: &str,
+    timestamp: i64,
+) -> Result<(), DbErr> {
+    let now = OffsetDateTime::now_utc();
+
+    entities::locked_asset::ActiveModel {
+        id: Set(id.next_id()),
+        class_id: Set(contract.class_id),
+        contract_id: Set(contract.id),
+        chain_id: Set(contract.chain_id.clone()),
+        asset_id: Set(contract.asset_id.clone()),
+        address: Set(extrinsic.from_address.clone()),
+        delegate: Set(extrinsic.to_address.clone()),
+        nonce: Set(lock_nonce(extrinsic)),
+        value: Set(value.to_owned()),
+        lock_reason: Set(LockReason::User),
+        expires_at: Set(expiry(extrinsic, timestamp)),
+        released_by: Set(None),
+        created_at: Set(Some(now)),
+        updated_at: Set(Some(now)),
+    }
+    .insert(db)
+    .await?;
+
+    Ok(())
+}
+
+// apply settles extrinsics in order against the ledger at the block timestamp,
+// those that cannot be settled are dropped with the reason, deploys only touch
+// contracts and are left to the caller
+pub async fn apply<C: ConnectionTrait>(
+    db: &C,
+    id: &IdService,
+    extrinsics: &mut [entities::extrinsic::Model],
+    timestamp: i64,
+) -> Result<(), DbErr> {
+    let mut issued = HashMap::new();
+
+    for extrinsic in extrinsics.iter_mut() {
+        if extrinsic.state == BlockState::Dropped
+            || extrinsic.operation == ExtrinsicOperation::Deploy
+        {
+            continue;
+        }
+
+        if let Err(reason) = settle(db, id, extrinsic, timestamp, &mut issued).await? {
+            extrinsic.state = BlockState::Dropped;
+            extrinsic.drop_reason = Some(reason);
+        }
+    }
+
+    Ok(())
+}
+
+// revert undoes the ledger changes of applied extrinsics, latest first, and
+// drops what they added to the history, it fails when the ledger cannot cover them
+pub async fn revert<C: ConnectionTrait>(
+    db: &C,
+    id: &IdService,
+    extrinsics: &[entities::extrinsic::Model],
+) -> Result<(), DbErr> {
+    let mut extrinsics = extrinsics.iter().collect::<Vec<_>>();
+    extrinsics
+        .sort_by_key(|extrinsic| (extrinsic.block_number, extrinsic.tx_index, extrinsic.index));
+
+    for extrinsic in extrinsics.iter().rev() {
+        if extrinsic.operation == ExtrinsicOperation::Deploy {
+            let now = OffsetDateTime::now_utc();
+
+            // the name is released, the canonical branch may deploy it again
+            if let Some(contract) = entities::contract::Entity::find()
+                .filter(entities::contract::Column::ChainId.eq(&extrinsic.chain_id))
+                .filter(entities::contract::Column::AssetId.eq(&extrinsic.asset_id))
+                .filter(entities::contract::Column::TxHash.eq(&extrinsic.tx_hash))
+                .filter(entities::contract::Column::State.ne(ContractState::Deployed))
+                .one(db)
+                .await?
+            {
+                let mut contract: entities::contract::ActiveModel = contract.into();
+                contract.state = Set(ContractState::Pending);
+                contract.tx_hash = Set(None);
+                contract.expires_at = Set(Some(now));
+                contract.updated_at = Set(Some(now));
+                contract.update(db).await?;
+            }
+
+            continue;
+        }
+
+        let contract = match contract(db, extrinsic).await? {
+            Some(contract) => contract,
+            None => continue,
+        };
+
+        if extrinsic.operation == ExtrinsicOperation::Unlock {
+            entities::locked_asset::Entity::update_many()
+                .col_expr(
+                    entities::locked_asset::Column::ReleasedBy,
+                    Expr::value(Option::<String>::None),
+                )
+                .filter(entities::locked_asset::Column::ContractId.eq(contract.id))
+                .filter(entities::locked_asset::Column::ReleasedBy.eq(lock_nonce(extrinsic)))
+                .exec(db)
+                .await?;
+
+            continue;
+        }
+
+        if extrinsic.operation == ExtrinsicOperation::Stake {
+            entities::locked_asset::Entity::delete_many()
+                .filter(entities::locked_asset::Column::ContractId.eq(contract.id))
+                .filter(entities::locked_asset::Column::Nonce.eq(lock_nonce(extrinsic)))
+                .exec(db)
+                .await?;
+
+            continue;
+        }
+
+        let (from, to) = (&extrinsic.from_address, &extrinsic.to_address);
+
+        match ClassType::from(contract.protocol) {
+            ClassType::Fungible => {
+                let amount = Uint256::from_str_prefixed(&extrinsic.value).unwrap_or_default();
+
+                let (debited, credited) = match extrinsic.operation {
+                    ExtrinsicOperation::Mint => (Some(to), None),
+                    ExtrinsicOperation::Transfer => (Some(to), Some(from)),
+                    ExtrinsicOperation::Burn => (None, Some(from)),
+                    _ => (None, None),
+                };
+
+                // the ledger no longer matches the extrinsics, the reorg is aborted
+                // rather than crediting tokens nobody gave up
+                if let Some(address) = debited {
+                    if !debit(db, id, &contract, address, &amount, None).await? {
+                        return Err(DbErr::Custom(format!(
+                            "balance of {} cannot cover reverted extrinsic {}",
+                            address, extrinsic.id
+                        )));
+                    }
+                }
+
+                if let Some(address) = credited {
+                    credit(db, id, &contract, address, &amount, None).await?;
+                }
+
+                if extrinsic.operation == ExtrinsicOperation::Mint {
+                    let minted = Uint256::from_str_prefixed(&contract.minted).unwrap_or_default();
+                    if minted < amount {
+                        return Err(DbErr::Custom(format!(
+                            "minted total of contract {} cannot cover reverted extrinsic {}",
+                            contract.id, extrinsic.id
+                        )));
+                    }
+
+                    issue(db, &contract, minted - amount).await?;
+                }
+            }
+            ClassType::NonFungible => {
+                let owner = match extrinsic.operation {
+                    ExtrinsicOperation::Mint => None,
+                    _ => Some(from.as_str()),
+                };
+
+                assign(db, id, &contract, &extrinsic.value, owner, None).await?;
+            }
+        }
+    }
+
+    entities::asset_change::Entity::delete_many()
+        .filter(
+            entities::asset_change::Column::ExtrinsicId
+                .is_in(extrinsics.iter().map(|extrinsic| extrinsic.id)),
+        )
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
@@ -1,11 +1,21 @@
 use eos420_primitives::{self as primitives, entities};
 
 mod cache;
+mod dispatcher;
+mod event;
 mod id;
+mod ledger;
 mod managers;
 mod utilities;
+mod verifier;
+mod watcher;
 
 pub use cache::CacheService;
+pub use dispatcher::{deliver, dispatch};
+pub use event::{Event, EventService};
 pub use id::IdService;
+pub use ledger::{apply, lock_nonce, revert};
 pub use managers::*;
 pub use utilities::*;
+pub use verifier::{statement, validate_key, verify, verify_ordinal};
+pub use watcher::watch;
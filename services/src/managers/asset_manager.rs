@@ -1,8 +1,9 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use sea_orm::{
-    sea_query::IntoCondition, ColumnTrait as _, DatabaseConnection, EntityTrait as _,
-    IntoSimpleExpr, Order, QueryFilter as _, QueryOrder as _, QuerySelect as _,
+    sea_query::{IntoCondition, Query},
+    ColumnTrait as _, Condition, DatabaseConnection, EntityTrait as _, IntoSimpleExpr, Order,
+    QueryFilter as _, QueryOrder as _, QuerySelect as _, Select,
 };
 
 use eos420_service_derive::cache;
@@ -10,7 +11,11 @@ use eos420_service_derive::cache;
 use crate::{
     entities::{self, ContractType},
     managers::{ContractManager, LockedAssetManager},
-    primitives::{v1::AssetResponse, Uint256},
+    primitives::{
+        bigint::Zero as _,
+        v1::{AssetResponse, PortfolioAssetResponse, PortfolioResponse},
+        Uint256,
+    },
     utilities::calculate_amount,
     CacheService, IdService,
 };
@@ -95,6 +100,184 @@ impl AssetManager {
         query.all(self.db.as_ref()).await.unwrap_or_default()
     }
 
+    // holdings selects the latest change up to block of every balance address had
+    // and of every token it touched, the ones still held by address at block are
+    // what it held then, emptied balances excluded
+    pub fn holdings(
+        &self,
+        chain_id: &str,
+        address: &str,
+        block: i64,
+        asset_ids: Option<&[String]>,
+    ) -> Select<entities::asset_change::Entity> {
+        let contracts = |protocols: [ContractType; 2]| {
+            Query::select()
+                .column(entities::contract::Column::Id)
+                .from(entities::contract::Entity)
+                .and_where(entities::contract::Column::ChainId.eq(chain_id))
+                .and_where(entities::contract::Column::Protocol.is_in(protocols))
+                .to_owned()
+        };
+
+        let mut touched = Query::select()
+            .column(entities::asset_change::Column::Value)
+            .from(entities::asset_change::Entity)
+            .and_where(entities::asset_change::Column::ChainId.eq(chain_id))
+            .and_where(entities::asset_change::Column::Address.eq(address))
+            .to_owned();
+
+        let mut balances = Query::select()
+            .expr(entities::asset_change::Column::Id.max())
+            .from(entities::asset_change::Entity)
+            .and_where(entities::asset_change::Column::ChainId.eq(chain_id))
+            .and_where(entities::asset_change::Column::Address.eq(address))
+            .and_where(entities::asset_change::Column::BlockNumber.lte(block))
+            .and_where(
+                entities::asset_change::Column::ContractId
+                    .in_subquery(contracts([ContractType::Erc20, ContractType::Eos20])),
+            )
+            .group_by_col(entities::asset_change::Column::ContractId)
+            .to_owned();
+
+        let mut tokens = Query::select()
+            .expr(entities::asset_change::Column::Id.max())
+            .from(entities::asset_change::Entity)
+            .and_where(entities::asset_change::Column::ChainId.eq(chain_id))
+            .and_where(entities::asset_change::Column::BlockNumber.lte(block))
+            .and_where(
+                entities::asset_change::Column::ContractId
+                    .in_subquery(contracts([ContractType::Erc721, ContractType::Eos420])),
+            )
+            .group_by_columns([
+                entities::asset_change::Column::ContractId,
+                entities::asset_change::Column::Value,
+            ])
+            .to_owned();
+
+        if let Some(asset_ids) = asset_ids {
+            let condition = entities::asset_change::Column::AssetId.is_in(asset_ids);
+
+            touched.and_where(condition.clone());
+            balances.and_where(condition.clone());
+            tokens.and_where(condition);
+        }
+
+        tokens.and_where(entities::asset_change::Column::Value.in_subquery(touched));
+
+        entities::asset_change::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(
+                        Condition::all()
+                            .add(entities::asset_change::Column::Id.in_subquery(balances))
+                            .add(entities::asset_change::Column::Value.ne("0x0")),
+                    )
+                    .add(entities::asset_change::Column::Id.in_subquery(tokens)),
+            )
+            .filter(entities::asset_change::Column::Address.eq(address))
+    }
+
+    // holding is the asset a change left behind
+    pub fn holding(&self, change: entities::asset_change::Model) -> entities::asset::Model {
+        entities::asset::Model {
+            id: change.id,
+            class_id: change.class_id,
+            contract_id: change.contract_id,
+            chain_id: change.chain_id,
+            asset_id: change.asset_id,
+            tx_hash: Some(change.tx_hash),
+            // fungible balances are hex encoded and never parse as an index
+            index: change.value.parse().ok(),
+            address: change.address,
+            value: change.value,
+            created_at: change.created_at,
+            updated_at: None,
+        }
+    }
+
+    pub async fn portfolio(&self, chain_id: &str, address: &str) -> Option<PortfolioResponse> {
+        let assets = entities::asset::Entity::find()
+            .select_only()
+            .column(entities::asset::Column::ContractId)
+            .column(entities::asset::Column::Value)
+            .filter(entities::asset::Column::ChainId.eq(chain_id))
+            .filter(entities::asset::Column::Address.eq(address))
+            .into_tuple::<(i64, String)>()
+            .all(self.db.as_ref())
+            .await
+            .ok()?;
+
+        let mut holdings = BTreeMap::<i64, Vec<String>>::new();
+        for (contract_id, value) in assets {
+            holdings.entry(contract_id).or_default().push(value);
+        }
+
+        let mut response = PortfolioResponse::builder();
+        response.with_chain_id(chain_id).with_address(address);
+
+        for (contract_id, values) in holdings {
+            let contract = match self.contract_manager.get(contract_id).await {
+                Some(contract) => contract,
+                None => continue,
+            };
+
+            // a contract that cannot be described is left out rather than failing the rest
+            let metadata = match self.contract_manager.dump(&contract, false).await {
+                Some(metadata) => metadata,
+                None => continue,
+            };
+
+            let locked = self
+                .locked_asset_manager
+                .unexpired(&contract, address)
+                .await;
+
+            let mut asset = PortfolioAssetResponse::builder();
+            asset.with_contract(metadata);
+
+            match contract.protocol {
+                ContractType::Erc20 | ContractType::Eos20 => {
+                    let balance = values.iter().fold(Uint256::zero(), |balance, value| {
+                        balance + Uint256::from_str_prefixed(value).unwrap_or_default()
+                    });
+
+                    let locked = locked.iter().fold(Uint256::zero(), |amount, locked| {
+                        amount + Uint256::from_str_prefixed(&locked.value).unwrap_or_default()
+                    });
+
+                    let available = if balance > locked {
+                        &balance - &locked
+                    } else {
+                        Uint256::zero()
+                    };
+
+                    let decimals = contract.decimals.unwrap_or_default();
+                    asset
+                        .with_amount(calculate_amount(&balance, decimals))
+                        .with_locked(calculate_amount(&locked, decimals))
+                        .with_available(calculate_amount(&available, decimals));
+                }
+                ContractType::Erc721 | ContractType::Eos420 => {
+                    let locked = locked
+                        .iter()
+                        .filter(|locked| values.contains(&locked.value))
+                        .count() as u64;
+
+                    asset
+                        .with_count(values.len() as u64)
+                        .with_locked(locked)
+                        .with_available((values.len() as u64).saturating_sub(locked));
+                }
+            }
+
+            if let Ok(asset) = asset.build() {
+                response.append(asset);
+            }
+        }
+
+        response.build().ok()
+    }
+
     pub async fn dump(&self, asset: &entities::asset::Model, agony: bool) -> Option<AssetResponse> {
         let contract = self.contract_manager.get(asset.contract_id).await?;
 
@@ -119,7 +302,24 @@ impl AssetManager {
         if agony {
             match contract.protocol {
                 ContractType::Erc20 | ContractType::Eos20 => {
-                    // TODO: Fungible
+                    let balance = Uint256::from_str_prefixed(&asset.value).unwrap_or_default();
+
+                    let locked = self
+                        .locked_asset_manager
+                        .amount(&asset.chain_id, &asset.asset_id, &asset.address)
+                        .await
+                        .unwrap_or_default();
+
+                    let available = if balance > locked {
+                        &balance - &locked
+                    } else {
+                        Uint256::zero()
+                    };
+
+                    let decimals = contract.decimals.unwrap_or_default();
+                    response
+                        .with_locked(calculate_amount(&locked, decimals))
+                        .with_available(calculate_amount(&available, decimals));
                 }
                 ContractType::Erc721 | ContractType::Eos420 => {
                     let locked = self
@@ -77,7 +77,9 @@ impl BlockManager {
             .with_chain_id(&block.chain_id)
             .with_number(block.block_number)
             .with_hash(&block.block_hash)
-            .with_state(block.state);
+            .with_state(block.state)
+            .with_transaction_count(block.transaction_count)
+            .with_extrinsic_count(block.extrinsic_count);
 
         if let Some(finalized_at) = block.finalized_at {
             response.with_finalized(true);
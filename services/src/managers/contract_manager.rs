@@ -1,16 +1,24 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use sea_orm::{
-    ColumnTrait as _, DatabaseConnection, EntityTrait as _, PaginatorTrait, QueryFilter as _,
-    QueryOrder as _, QuerySelect,
+    sea_query::{Alias, Expr, Func, IntoColumnRef, Query, SimpleExpr},
+    ActiveModelTrait as _, ColumnTrait as _, DatabaseConnection, DbErr, EntityTrait as _,
+    PaginatorTrait, QueryFilter as _, QueryOrder as _, QuerySelect, Select, Set,
+    TransactionTrait as _,
 };
+use time::OffsetDateTime;
 
 use eos420_service_derive::cache;
 
 use crate::{
-    entities::{self, ClassType},
+    entities::{self, ClassType, ContractState, ContractType},
     managers::ClassManager,
-    primitives::{bigint::FromPrimitive as _, v1::ContractResponse, Uint256},
+    primitives::{
+        bigint::{FromPrimitive as _, ToPrimitive as _},
+        v1::{ContractResponse, HolderExportResponse, HolderResponse},
+        Setting, Uint256,
+    },
+    utilities::{calculate_amount, format_amount},
     CacheService, IdService,
 };
 
@@ -20,6 +28,7 @@ pub struct ContractManager {
     pub cache: Arc<CacheService<entities::contract::Model>>,
     pub db: Arc<DatabaseConnection>,
     pub id: Arc<IdService>,
+    pub setting: Arc<Setting>,
     pub class_manager: Arc<ClassManager>,
 }
 
@@ -43,6 +52,106 @@ impl ContractManager {
             .ok()?
     }
 
+    pub async fn deploy(&self, id: i64) -> Option<entities::contract::Model> {
+        // deploys are polled for state changes, so they are never served from cache
+        entities::contract::Entity::find_by_id(id)
+            .one(self.db.as_ref())
+            .await
+            .ok()?
+    }
+
+    pub fn expired(&self, contract: &entities::contract::Model) -> bool {
+        contract.state == ContractState::Pending
+            && contract
+                .expires_at
+                .is_some_and(|expires_at| expires_at <= OffsetDateTime::now_utc())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn reserve(
+        &self,
+        chain_id: &str,
+        name: &str,
+        protocol: ContractType,
+        owner: Option<&str>,
+        to_address: &str,
+        expiry: Duration,
+        verifying_key: Option<&str>,
+    ) -> Result<entities::contract::Model, DbErr> {
+        let now = OffsetDateTime::now_utc();
+
+        let txn = self.db.begin().await?;
+
+        // release an expired reservation of the same name before taking it over
+        let expired = entities::contract::Entity::find()
+            .filter(entities::contract::Column::ChainId.eq(chain_id))
+            .filter(entities::contract::Column::AssetId.eq(name))
+            .filter(entities::contract::Column::State.eq(ContractState::Pending))
+            .filter(entities::contract::Column::ExpiresAt.lte(now))
+            .all(&txn)
+            .await?;
+
+        for contract in &expired {
+            entities::contract::Entity::delete_by_id(contract.id)
+                .exec(&txn)
+                .await?;
+
+            entities::class::Entity::delete_by_id(contract.class_id)
+                .exec(&txn)
+                .await?;
+        }
+
+        let class = entities::class::ActiveModel {
+            id: Set(self.id.next_id()),
+            r#type: Set(protocol.into()),
+            name: Set(name.to_owned()),
+            symbol: Set(name.to_owned()),
+            owner: Set(owner.map(ToOwned::to_owned)),
+            description: Set(String::new()),
+            cover_image_uri: Set(String::new()),
+            image_uri_template: Set(None),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+        }
+        .insert(&txn)
+        .await?;
+
+        // the unique index on chain and asset id rejects a concurrent reservation
+        let contract = entities::contract::ActiveModel {
+            id: Set(self.id.next_id()),
+            class_id: Set(class.id),
+            chain_id: Set(chain_id.to_owned()),
+            asset_id: Set(name.to_owned()),
+            address: Set(to_address.to_owned()),
+            owner: Set(owner.map(ToOwned::to_owned)),
+            protocol: Set(protocol),
+            decimals: Set(None),
+            identifier: Set(None),
+            max_supply: Set(None),
+            mint_limit: Set(None),
+            minted: Set("0x0".to_owned()),
+            not_before: Set(None),
+            tx_hash: Set(None),
+            state: Set(ContractState::Pending),
+            deployed_at: Set(None),
+            expires_at: Set(Some(now + expiry)),
+            verifying_key: Set(verifying_key.map(ToOwned::to_owned)),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+        }
+        .insert(&txn)
+        .await?;
+
+        txn.commit().await?;
+
+        self.cache.invalidate((chain_id, name)).await;
+        for contract in &expired {
+            self.cache.invalidate(contract.id).await;
+        }
+
+        Ok(contract)
+    }
+
     pub async fn supply(&self, contract: &entities::contract::Model) -> Option<Uint256> {
         match contract.protocol.into() {
             ClassType::Fungible => None,
@@ -58,29 +167,341 @@ impl ContractManager {
         }
     }
 
-    pub async fn holder(
+    // total is what holder shares are measured against, the max supply of fungible
+    // contracts and the minted tokens of non-fungible ones
+    pub async fn total(&self, contract: &entities::contract::Model) -> f64 {
+        let total = match contract.protocol.into() {
+            ClassType::Fungible => contract
+                .max_supply
+                .as_deref()
+                .and_then(|supply| Uint256::from_str_prefixed(supply).ok()),
+            ClassType::NonFungible => self.supply(contract).await,
+        };
+
+        total.and_then(|total| total.to_f64()).unwrap_or_default()
+    }
+
+    // balances ranks the holders of a fungible contract in the database, the ledger
+    // keeps one row per holder with a canonical hex value so that a longer value
+    // is a larger one
+    pub async fn balances(
         &self,
         contract: &entities::contract::Model,
-        limit: u64,
-    ) -> Vec<(String, i64)> {
-        entities::asset::Entity::find()
+        offset: u64,
+        limit: Option<u64>,
+    ) -> Result<Vec<(String, Uint256)>, DbErr> {
+        let balances = entities::asset::Entity::find()
             .select_only()
             .column(entities::asset::Column::Address)
-            .column_as(
-                entities::asset::Column::Id.count(),
-                entities::asset::GroupAs::Count,
-            )
+            .column(entities::asset::Column::Value)
             .filter(entities::asset::Column::ContractId.eq(contract.id))
-            .group_by(entities::asset::Column::Address)
-            .order_by_desc(entities::asset::GroupAs::Count)
+            .order_by_desc(length(entities::asset::Column::Value))
+            .order_by_desc(entities::asset::Column::Value)
+            .order_by_asc(entities::asset::Column::Address)
+            .offset(offset)
             .limit(limit)
-            .into_values::<_, entities::asset::GroupAs>()
+            .into_tuple::<(String, String)>()
             .all(self.db.as_ref())
-            .await
-            .unwrap_or_default()
+            .await?
+            .into_iter()
+            .map(|(address, value)| {
+                (
+                    address,
+                    Uint256::from_str_prefixed(&value).unwrap_or_default(),
+                )
+            })
+            .collect();
+
+        Ok(balances)
     }
 
-    pub async fn holder_count(&self, contract: &entities::contract::Model) -> u64 {
+    // latest selects the change up to block that is current for every holder of a
+    // fungible contract or every token of a non-fungible one, ids grow with blocks
+    fn latest(
+        &self,
+        contract: &entities::contract::Model,
+        block: i64,
+    ) -> Select<entities::asset_change::Entity> {
+        let fungible = ClassType::from(contract.protocol) == ClassType::Fungible;
+
+        let key = match fungible {
+            true => entities::asset_change::Column::Address,
+            false => entities::asset_change::Column::Value,
+        };
+
+        let latest = Query::select()
+            .expr(entities::asset_change::Column::Id.max())
+            .from(entities::asset_change::Entity)
+            .and_where(entities::asset_change::Column::ContractId.eq(contract.id))
+            .and_where(entities::asset_change::Column::BlockNumber.lte(block))
+            .group_by_col(key)
+            .to_owned();
+
+        // burned tokens are owned by nobody and emptied balances hold nothing
+        let select = entities::asset_change::Entity::find()
+            .filter(entities::asset_change::Column::Id.in_subquery(latest))
+            .filter(entities::asset_change::Column::Address.ne(""));
+
+        match fungible {
+            true => select.filter(entities::asset_change::Column::Value.ne("0x0")),
+            false => select,
+        }
+    }
+
+    // snapshot ranks what every address held at block from the asset history,
+    // balances for fungible contracts and token counts for non-fungible ones
+    pub async fn snapshot(
+        &self,
+        contract: &entities::contract::Model,
+        block: i64,
+        offset: u64,
+        limit: Option<u64>,
+    ) -> Result<Vec<(String, Uint256)>, DbErr> {
+        let select = self
+            .latest(contract, block)
+            .select_only()
+            .column(entities::asset_change::Column::Address);
+
+        let snapshot = match contract.protocol.into() {
+            ClassType::Fungible => select
+                .column(entities::asset_change::Column::Value)
+                .order_by_desc(length(entities::asset_change::Column::Value))
+                .order_by_desc(entities::asset_change::Column::Value)
+                .order_by_asc(entities::asset_change::Column::Address)
+                .offset(offset)
+                .limit(limit)
+                .into_tuple::<(String, String)>()
+                .all(self.db.as_ref())
+                .await?
+                .into_iter()
+                .map(|(address, value)| {
+                    (
+                        address,
+                        Uint256::from_str_prefixed(&value).unwrap_or_default(),
+                    )
+                })
+                .collect(),
+            ClassType::NonFungible => select
+                .column_as(entities::asset_change::Column::Id.count(), "count")
+                .group_by(entities::asset_change::Column::Address)
+                .order_by_desc(entities::asset_change::Column::Id.count())
+                .order_by_asc(entities::asset_change::Column::Address)
+                .offset(offset)
+                .limit(limit)
+                .into_tuple::<(String, i64)>()
+                .all(self.db.as_ref())
+                .await?
+                .into_iter()
+                .map(|(address, count)| (address, Uint256::from_i64(count).unwrap_or_default()))
+                .collect(),
+        };
+
+        Ok(snapshot)
+    }
+
+    pub async fn holder(
+        &self,
+        contract: &entities::contract::Model,
+        at_block: Option<i64>,
+        page: u64,
+        size: u64,
+    ) -> Vec<HolderResponse> {
+        match contract.protocol.into() {
+            ClassType::Fungible => {
+                let balances = match at_block {
+                    Some(block) => {
+                        self.snapshot(contract, block, page * size, Some(size))
+                            .await
+                    }
+                    None => self.balances(contract, page * size, Some(size)).await,
+                }
+                .unwrap_or_default();
+
+                let supply = self.total(contract).await;
+
+                balances
+                    .into_iter()
+                    .filter_map(|(address, balance)| {
+                        let share = match supply {
+                            supply if supply > 0f64 => {
+                                balance.to_f64().unwrap_or_default() / supply
+                            }
+                            _ => 0f64,
+                        };
+
+                        let mut response = HolderResponse::builder();
+                        response.with_address(address).with_share(share);
+
+                        match contract.decimals {
+                            Some(decimals) => {
+                                response.with_amount(calculate_amount(&balance, decimals))
+                            }
+                            None => response.with_amount(balance),
+                        };
+
+                        response.build().ok()
+                    })
+                    .collect()
+            }
+            ClassType::NonFungible if at_block.is_some() => {
+                let counts = self
+                    .snapshot(
+                        contract,
+                        at_block.unwrap_or_default(),
+                        page * size,
+                        Some(size),
+                    )
+                    .await
+                    .unwrap_or_default();
+
+                let supply = self.total(contract).await;
+
+                counts
+                    .into_iter()
+                    .filter_map(|(address, count)| {
+                        let count = count.to_u64().unwrap_or_default();
+
+                        let share = match supply {
+                            supply if supply > 0f64 => count as f64 / supply,
+                            _ => 0f64,
+                        };
+
+                        HolderResponse::builder()
+                            .with_address(address)
+                            .with_count(count)
+                            .with_share(share)
+                            .build()
+                            .ok()
+                    })
+                    .collect()
+            }
+            ClassType::NonFungible => {
+                let supply = self.total(contract).await;
+
+                let holders = entities::asset::Entity::find()
+                    .select_only()
+                    .column(entities::asset::Column::Address)
+                    .column_as(
+                        entities::asset::Column::Id.count(),
+                        entities::asset::GroupAs::Count,
+                    )
+                    .filter(entities::asset::Column::ContractId.eq(contract.id))
+                    .group_by(entities::asset::Column::Address)
+                    .order_by_desc(entities::asset::GroupAs::Count)
+                    .order_by_asc(entities::asset::Column::Address)
+                    .offset(page * size)
+                    .limit(size)
+                    .into_values::<(String, i64), entities::asset::GroupAs>()
+                    .all(self.db.as_ref())
+                    .await
+                    .unwrap_or_default();
+
+                holders
+                    .into_iter()
+                    .filter_map(|(address, count)| {
+                        let share = match supply {
+                            supply if supply > 0f64 => count as f64 / supply,
+                            _ => 0f64,
+                        };
+
+                        HolderResponse::builder()
+                            .with_address(address)
+                            .with_count(count as u64)
+                            .with_share(share)
+                            .build()
+                            .ok()
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    // holders ranks holders from offset on, balances for fungible contracts and
+    // token counts for non-fungible ones
+    pub async fn holders(
+        &self,
+        contract: &entities::contract::Model,
+        at_block: Option<i64>,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<(String, Uint256)>, DbErr> {
+        if let Some(block) = at_block {
+            return self.snapshot(contract, block, offset, Some(limit)).await;
+        }
+
+        match contract.protocol.into() {
+            ClassType::Fungible => self.balances(contract, offset, Some(limit)).await,
+            ClassType::NonFungible => Ok(entities::asset::Entity::find()
+                .select_only()
+                .column(entities::asset::Column::Address)
+                .column_as(
+                    entities::asset::Column::Id.count(),
+                    entities::asset::GroupAs::Count,
+                )
+                .filter(entities::asset::Column::ContractId.eq(contract.id))
+                .group_by(entities::asset::Column::Address)
+                .order_by_desc(entities::asset::GroupAs::Count)
+                .order_by_asc(entities::asset::Column::Address)
+                .offset(offset)
+                .limit(limit)
+                .into_values::<(String, i64), entities::asset::GroupAs>()
+                .all(self.db.as_ref())
+                .await?
+                .into_iter()
+                .map(|(address, count)| (address, Uint256::from_i64(count).unwrap_or_default()))
+                .collect()),
+        }
+    }
+
+    // export formats ranked holders for export, supply is the total of the contract
+    pub fn export(
+        &self,
+        contract: &entities::contract::Model,
+        holders: Vec<(String, Uint256)>,
+        supply: f64,
+    ) -> Vec<HolderExportResponse> {
+        holders
+            .into_iter()
+            .filter_map(|(address, amount)| {
+                let share = match supply {
+                    supply if supply > 0f64 => amount.to_f64().unwrap_or_default() / supply,
+                    _ => 0f64,
+                };
+
+                let amount = format_amount(&amount, contract.decimals.unwrap_or_default());
+
+                HolderExportResponse::builder()
+                    .with_address(address)
+                    .with_amount(amount)
+                    .with_share(share)
+                    .build()
+                    .ok()
+            })
+            .collect()
+    }
+
+    pub async fn holder_count(
+        &self,
+        contract: &entities::contract::Model,
+        at_block: Option<i64>,
+    ) -> u64 {
+        if let Some(block) = at_block {
+            let select = self.latest(contract, block);
+
+            return match contract.protocol.into() {
+                ClassType::Fungible => select.count(self.db.as_ref()).await,
+                ClassType::NonFungible => {
+                    select
+                        .select_only()
+                        .column(entities::asset_change::Column::Address)
+                        .group_by(entities::asset_change::Column::Address)
+                        .count(self.db.as_ref())
+                        .await
+                }
+            }
+            .unwrap_or_default();
+        }
+
         entities::asset::Entity::find()
             .select_only()
             .column(entities::asset::Column::Address)
@@ -114,9 +535,21 @@ impl ContractManager {
             .with_cover_image_uri(&class.cover_image_uri)
             .with_state(contract.state);
 
-        response
-            .with_to_address(&contract.address)
-            .with_fee(Uint256::from_str_prefixed("0x1A055690D9DB80000").unwrap());
+        if contract.state != ContractState::Deployed {
+            response.with_deploy_id(contract.id);
+        }
+
+        if let Some(expires_at) = contract.expires_at {
+            if contract.state == ContractState::Pending {
+                response.with_expires_at(expires_at);
+            }
+        }
+
+        response.with_to_address(&contract.address);
+
+        if let Some(chain) = self.setting.chain(&contract.chain_id) {
+            response.with_fee(chain.fee().clone());
+        }
 
         if let Some(owner) = &contract.owner {
             response.with_owner(owner);
@@ -153,9 +586,115 @@ impl ContractManager {
         if agony {
             response
                 .with_supply(self.supply(contract).await.unwrap_or_default())
-                .with_holder_count(self.holder_count(contract).await);
+                .with_holder_count(self.holder_count(contract, None).await);
         }
 
         response.build().ok()
     }
 }
+
+// length orders canonical hex values numerically together with the value itself
+fn length(column: impl IntoColumnRef) -> SimpleExpr {
+    Func::cust(Alias::new("LENGTH"))
+        .arg(Expr::col(column))
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use di::Injectable as _;
+    use migration::{Migrator, MigratorTrait as _};
+    use sea_orm::{ConnectOptions, Database};
+    use serde_json::json;
+
+    use super::*;
+
+    async fn manager() -> ContractManager {
+        // every connection to an in-memory database opens a database of its own
+        let mut options = ConnectOptions::new("sqlite::memory:");
+        options.max_connections(1);
+
+        let db = Database::connect(options).await.unwrap();
+        Migrator::up(&db, None).await.unwrap();
+
+        let mut provider = di::ServiceCollection::new();
+        provider.add(di::singleton_as_self().from(|_| Arc::new(IdService::new(1))));
+        provider.add(di::singleton_as_self().from(|_| Arc::new(Setting::default())));
+        provider.add(di::singleton_as_self().from(move |_| Arc::new(db.clone())));
+        provider.add(CacheService::<entities::class::Model>::singleton());
+        provider.add(CacheService::<entities::contract::Model>::singleton());
+        provider.add(ClassManager::scoped());
+        provider.add(ContractManager::scoped());
+
+        let provider = provider.build_provider().unwrap();
+        provider.get_required::<ContractManager>().as_ref().clone()
+    }
+
+    async fn contract(manager: &ContractManager) -> entities::contract::Model {
+        entities::contract::ActiveModel {
+            id: Set(1),
+            class_id: Set(1),
+            chain_id: Set("1".to_owned()),
+            asset_id: Set("nft".to_owned()),
+            address: Set("0xcc".to_owned()),
+            protocol: Set(ContractType::Eos420),
+            state: Set(ContractState::Deployed),
+            ..Default::default()
+        }
+        .insert(manager.db.as_ref())
+        .await
+        .unwrap()
+    }
+
+    // change records that address received token at block
+    async fn change(manager: &ContractManager, id: i64, block: i64, token: &str, address: &str) {
+        entities::asset_change::ActiveModel {
+            id: Set(id),
+            class_id: Set(1),
+            contract_id: Set(1),
+            chain_id: Set("1".to_owned()),
+            asset_id: Set("nft".to_owned()),
+            extrinsic_id: Set(id),
+            block_number: Set(block),
+            tx_hash: Set(format!("0x{:02x}", id)),
+            address: Set(address.to_owned()),
+            value: Set(token.to_owned()),
+            ..Default::default()
+        }
+        .insert(manager.db.as_ref())
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn holder_pages_non_fungible_snapshots() {
+        let manager = manager().await;
+        let contract = contract(&manager).await;
+
+        change(&manager, 1, 1, "1", "0xaa").await;
+        change(&manager, 2, 1, "2", "0xaa").await;
+        change(&manager, 3, 1, "3", "0xbb").await;
+        // moved after the snapshot
+        change(&manager, 4, 2, "2", "0xbb").await;
+
+        let holders = |page| {
+            let manager = manager.clone();
+            let contract = contract.clone();
+            async move {
+                manager
+                    .holder(&contract, Some(1), page, 1)
+                    .await
+                    .into_iter()
+                    .map(|holder| {
+                        let holder = serde_json::to_value(holder).unwrap();
+                        (holder["address"].clone(), holder["count"].clone())
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(holders(0).await, [(json!("0xaa"), json!(2))]);
+        assert_eq!(holders(1).await, [(json!("0xbb"), json!(1))]);
+        assert!(holders(2).await.is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use sea_orm::{
     sea_query::IntoCondition, ColumnTrait as _, DatabaseConnection, EntityTrait as _,
@@ -8,10 +8,13 @@ use sea_orm::{
 use eos420_service_derive::cache;
 
 use crate::{
-    entities,
+    entities::{self, BlockState},
     managers::{ClassManager, ContractManager, TransactionManager},
-    primitives::{v1::ExtrinsicResponse, Uint256},
-    utilities::calculate_amount,
+    primitives::{
+        v1::{ActivityDirection, ActivityResponse, ExtrinsicExportResponse, ExtrinsicResponse},
+        Uint256,
+    },
+    utilities::{calculate_amount, format_amount},
     CacheService, IdService,
 };
 
@@ -44,6 +47,25 @@ impl ExtrinsicManager {
             .ok()?
     }
 
+    // legs returns every extrinsic a transaction produced ordered by index, the
+    // latest row wins for legs a reorg indexed more than once
+    pub async fn legs(&self, chain_id: &str, tx_hash: &str) -> Vec<entities::extrinsic::Model> {
+        let extrinsics = entities::extrinsic::Entity::find()
+            .filter(entities::extrinsic::Column::ChainId.eq(chain_id))
+            .filter(entities::extrinsic::Column::TxHash.eq(tx_hash))
+            .order_by_desc(entities::extrinsic::Column::Id)
+            .all(self.db.as_ref())
+            .await
+            .unwrap_or_default();
+
+        let mut legs = BTreeMap::new();
+        for extrinsic in extrinsics {
+            legs.entry(extrinsic.index).or_insert(extrinsic);
+        }
+
+        legs.into_values().collect()
+    }
+
     pub async fn query<C: IntoSimpleExpr, F: IntoCondition>(
         &self,
         filter: Vec<F>,
@@ -67,6 +89,46 @@ impl ExtrinsicManager {
         query.all(self.db.as_ref()).await.unwrap_or_default()
     }
 
+    pub async fn activity(
+        &self,
+        extrinsic: &entities::extrinsic::Model,
+        address: &str,
+    ) -> Option<ActivityResponse> {
+        let contract = self
+            .contract_manager
+            .find(&extrinsic.chain_id, &extrinsic.asset_id)
+            .await?;
+
+        let direction = ActivityDirection::new(
+            extrinsic.operation,
+            &extrinsic.from_address,
+            &extrinsic.to_address,
+            address,
+        );
+
+        let sign = match extrinsic.state {
+            BlockState::Finalized => {
+                direction.sign(&extrinsic.from_address, &extrinsic.to_address, address)
+            }
+            _ => 0,
+        };
+
+        let mut response = ActivityResponse::builder();
+        response
+            .with_extrinsic(self.dump(extrinsic, false).await?)
+            .with_direction(direction);
+
+        if let Some(decimals) = contract.decimals {
+            let amount = Uint256::from_str_prefixed(&extrinsic.value).unwrap_or_default();
+            let amount = calculate_amount(&amount, decimals);
+            response.with_change(amount * sign as f64);
+        } else {
+            response.with_change(sign as i64);
+        }
+
+        response.build().ok()
+    }
+
     pub async fn dump(
         &self,
         extrinsic: &entities::extrinsic::Model,
@@ -110,4 +172,51 @@ impl ExtrinsicManager {
 
         response.build().ok()
     }
+
+    pub async fn export(
+        &self,
+        extrinsic: &entities::extrinsic::Model,
+    ) -> Option<ExtrinsicExportResponse> {
+        let contract = self
+            .contract_manager
+            .find(&extrinsic.chain_id, &extrinsic.asset_id)
+            .await?;
+
+        let mut response = ExtrinsicExportResponse::builder();
+        response
+            .with_chain_id(&extrinsic.chain_id)
+            .with_tx_hash(&extrinsic.tx_hash)
+            .with_index(extrinsic.index)
+            .with_asset_id(&extrinsic.asset_id)
+            .with_protocol(extrinsic.protocol)
+            .with_from_address(&extrinsic.from_address)
+            .with_to_address(&extrinsic.to_address)
+            .with_operation(extrinsic.operation)
+            .with_state(extrinsic.state);
+
+        if let Some(block_number) = extrinsic.block_number {
+            response.with_block_number(block_number);
+        }
+
+        if let Some(block_hash) = &extrinsic.block_hash {
+            response.with_block_hash(block_hash);
+        }
+
+        if let Some(tx_index) = extrinsic.tx_index {
+            response.with_tx_index(tx_index);
+        }
+
+        if let Some(drop_reason) = extrinsic.drop_reason {
+            response.with_drop_reason(drop_reason);
+        }
+
+        if let Some(decimals) = contract.decimals {
+            let amount = Uint256::from_str_prefixed(&extrinsic.value).unwrap_or_default();
+            response.with_amount(format_amount(&amount, decimals));
+        } else {
+            response.with_identifier(&extrinsic.value);
+        }
+
+        response.build().ok()
+    }
 }
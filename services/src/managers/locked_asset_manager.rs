@@ -5,9 +5,13 @@ use sea_orm::{
     IntoSimpleExpr, Order, QueryFilter as _, QueryOrder as _, QuerySelect as _,
 };
 
+use time::OffsetDateTime;
+
 use crate::{
     entities::{self, ContractType},
     managers::ContractManager,
+    primitives::{bigint::Zero as _, v1::LockResponse, Uint256},
+    utilities::calculate_amount,
     IdService,
 };
 
@@ -28,6 +32,8 @@ impl LockedAssetManager {
     ) -> Option<entities::locked_asset::Model> {
         let contract = self.contract_manager.find(chain_id, asset_id).await?;
 
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+
         match contract.protocol {
             ContractType::Erc721 | ContractType::Eos420 => (),
             _ => return None,
@@ -36,19 +42,56 @@ impl LockedAssetManager {
         entities::locked_asset::Entity::find()
             .filter(entities::locked_asset::Column::ContractId.eq(contract.id))
             .filter(entities::locked_asset::Column::Value.eq(identifier))
+            .filter(entities::locked_asset::Column::ExpiresAt.gt(now))
+            .filter(entities::locked_asset::Column::ReleasedBy.is_null())
             .order_by_desc(entities::locked_asset::Column::Id)
             .one(self.db.as_ref())
             .await
             .ok()?
     }
 
+    pub async fn amount(&self, chain_id: &str, asset_id: &str, address: &str) -> Option<Uint256> {
+        let contract = self.contract_manager.find(chain_id, asset_id).await?;
+
+        match contract.protocol {
+            ContractType::Erc20 | ContractType::Eos20 => (),
+            _ => return None,
+        }
+
+        let locked = self.unexpired(&contract, address).await;
+
+        let amount = locked.iter().fold(Uint256::zero(), |amount, locked| {
+            amount + Uint256::from_str_prefixed(&locked.value).unwrap_or_default()
+        });
+
+        Some(amount)
+    }
+
+    pub async fn unexpired(
+        &self,
+        contract: &entities::contract::Model,
+        address: &str,
+    ) -> Vec<entities::locked_asset::Model> {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+
+        entities::locked_asset::Entity::find()
+            .filter(entities::locked_asset::Column::ContractId.eq(contract.id))
+            .filter(entities::locked_asset::Column::Address.eq(address))
+            .filter(entities::locked_asset::Column::ExpiresAt.gt(now))
+            .filter(entities::locked_asset::Column::ReleasedBy.is_null())
+            .order_by_desc(entities::locked_asset::Column::Id)
+            .all(self.db.as_ref())
+            .await
+            .unwrap_or_default()
+    }
+
     pub async fn query<C: IntoSimpleExpr, F: IntoCondition>(
         &self,
         filter: Vec<F>,
         order: Vec<(C, Order)>,
         limit: Option<u64>,
-    ) -> Vec<entities::asset::Model> {
-        let mut query = entities::asset::Entity::find();
+    ) -> Vec<entities::locked_asset::Model> {
+        let mut query = entities::locked_asset::Entity::find();
 
         for f in filter {
             query = query.filter(f);
@@ -64,4 +107,42 @@ impl LockedAssetManager {
 
         query.all(self.db.as_ref()).await.unwrap_or_default()
     }
+
+    pub fn active(&self, lock: &entities::locked_asset::Model) -> bool {
+        lock.released_by.is_none() && lock.expires_at > OffsetDateTime::now_utc().unix_timestamp()
+    }
+
+    pub async fn dump(&self, lock: &entities::locked_asset::Model) -> Option<LockResponse> {
+        let contract = self.contract_manager.get(lock.contract_id).await?;
+
+        let mut response = LockResponse::builder();
+
+        let metadata = self.contract_manager.dump(&contract, false).await?;
+
+        response
+            .with_contract(metadata)
+            .with_address(&lock.address)
+            .with_delegate(&lock.delegate)
+            .with_nonce(&lock.nonce)
+            .with_lock_reason(lock.lock_reason)
+            .with_released(lock.released_by.is_some())
+            .with_active(self.active(lock));
+
+        // locks without an expiry carry the largest timestamp
+        if let Ok(expires_at) = OffsetDateTime::from_unix_timestamp(lock.expires_at) {
+            response.with_expires_at(expires_at);
+        }
+
+        match contract.decimals {
+            Some(decimals) => {
+                let amount = Uint256::from_str_prefixed(&lock.value).unwrap_or_default();
+                response.with_amount(calculate_amount(&amount, decimals));
+            }
+            None => {
+                response.with_identifier(&lock.value);
+            }
+        }
+
+        response.build().ok()
+    }
 }
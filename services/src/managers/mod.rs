@@ -5,6 +5,7 @@ mod contract_manager;
 mod extrinsic_manager;
 mod locked_asset_manager;
 mod transaction_manager;
+mod webhook_manager;
 
 pub use asset_manager::AssetManager;
 pub use block_manager::BlockManager;
@@ -13,3 +14,4 @@ pub use contract_manager::ContractManager;
 pub use extrinsic_manager::ExtrinsicManager;
 pub use locked_asset_manager::LockedAssetManager;
 pub use transaction_manager::TransactionManager;
+pub use webhook_manager::WebhookManager;
@@ -0,0 +1,496 @@
+use std::{sync::Arc, time::Duration};
+
+use hmac::{Hmac, Mac as _};
+use rand::RngCore as _;
+use sea_orm::{
+    sea_query::{Expr, OnConflict},
+    ActiveModelTrait as _, ColumnTrait as _, DatabaseConnection, DbErr, EntityTrait as _,
+    QueryFilter as _, QueryOrder as _, QuerySelect as _, Set,
+};
+use sha2::Sha256;
+use time::OffsetDateTime;
+
+use crate::{
+    entities::{self, BlockState, DeliveryState},
+    managers::ExtrinsicManager,
+    primitives::{
+        v1::{WebhookDeliveryResponse, WebhookPayload, WebhookRequest, WebhookResponse},
+        WebhookSetting,
+    },
+    utilities::resolve_public,
+    IdService,
+};
+
+#[di::injectable]
+#[derive(Clone)]
+pub struct WebhookManager {
+    pub db: Arc<DatabaseConnection>,
+    pub id: Arc<IdService>,
+    pub extrinsic_manager: Arc<ExtrinsicManager>,
+}
+
+impl WebhookManager {
+    pub async fn get(&self, id: i64) -> Option<entities::webhook::Model> {
+        entities::webhook::Entity::find_by_id(id)
+            .one(self.db.as_ref())
+            .await
+            .ok()?
+    }
+
+    pub async fn create(
+        &self,
+        chain_id: &str,
+        url: &str,
+        request: &WebhookRequest,
+    ) -> Option<entities::webhook::Model> {
+        let now = OffsetDateTime::now_utc();
+
+        let secret = match &request.secret {
+            Some(secret) => secret.clone(),
+            None => {
+                let mut secret = [0u8; 32];
+                rand::thread_rng().fill_bytes(&mut secret);
+                hex::encode(secret)
+            }
+        };
+
+        let webhook = entities::webhook::ActiveModel {
+            id: Set(self.id.next_id()),
+            chain_id: Set(chain_id.to_owned()),
+            url: Set(url.to_owned()),
+            secret: Set(secret),
+            asset_id: Set(request.asset_id.clone()),
+            address: Set(request.address.clone()),
+            operation: Set(request.operation),
+            enabled: Set(request.enabled.unwrap_or(true)),
+            created_at: Set(Some(now)),
+            updated_at: Set(Some(now)),
+        };
+
+        webhook.insert(self.db.as_ref()).await.ok()
+    }
+
+    pub async fn update(
+        &self,
+        webhook: entities::webhook::Model,
+        request: &WebhookRequest,
+    ) -> Option<entities::webhook::Model> {
+        let mut webhook: entities::webhook::ActiveModel = webhook.into();
+
+        if let Some(url) = &request.url {
+            webhook.url = Set(url.clone());
+        }
+
+        if let Some(secret) = &request.secret {
+            webhook.secret = Set(secret.clone());
+        }
+
+        if let Some(asset_id) = &request.asset_id {
+            webhook.asset_id = Set(Some(asset_id.clone()).filter(|asset_id| !asset_id.is_empty()));
+        }
+
+        if let Some(address) = &request.address {
+            webhook.address = Set(Some(address.clone()).filter(|address| !address.is_empty()));
+        }
+
+        if let Some(operation) = request.operation {
+            webhook.operation = Set(Some(operation));
+        }
+
+        if let Some(enabled) = request.enabled {
+            webhook.enabled = Set(enabled);
+        }
+
+        webhook.updated_at = Set(Some(OffsetDateTime::now_utc()));
+
+        webhook.update(self.db.as_ref()).await.ok()
+    }
+
+    pub async fn delete(&self, webhook: &entities::webhook::Model) -> bool {
+        let deleted = entities::webhook::Entity::delete_by_id(webhook.id)
+            .exec(self.db.as_ref())
+            .await
+            .map(|result| result.rows_affected > 0)
+            .unwrap_or_default();
+
+        if deleted {
+            let _ = entities::webhook_delivery::Entity::delete_many()
+                .filter(entities::webhook_delivery::Column::WebhookId.eq(webhook.id))
+                .exec(self.db.as_ref())
+                .await;
+        }
+
+        deleted
+    }
+
+    pub async fn dump(
+        &self,
+        webhook: &entities::webhook::Model,
+        reveal: bool,
+    ) -> Option<WebhookResponse> {
+        let mut response = WebhookResponse::builder();
+        response
+            .with_id(webhook.id)
+            .with_chain_id(&webhook.chain_id)
+            .with_url(&webhook.url)
+            .with_enabled(webhook.enabled);
+
+        if reveal {
+            response.with_secret(&webhook.secret);
+        }
+
+        if let Some(asset_id) = &webhook.asset_id {
+            response.with_asset_id(asset_id);
+        }
+
+        if let Some(address) = &webhook.address {
+            response.with_address(address);
+        }
+
+        if let Some(operation) = webhook.operation {
+            response.with_operation(operation);
+        }
+
+        if let Some(created_at) = webhook.created_at {
+            response.with_created_at(created_at);
+        }
+
+        response.build().ok()
+    }
+
+    pub async fn dump_delivery(
+        &self,
+        delivery: &entities::webhook_delivery::Model,
+    ) -> Option<WebhookDeliveryResponse> {
+        let mut response = WebhookDeliveryResponse::builder();
+        response
+            .with_id(delivery.id)
+            .with_event(delivery.event)
+            .with_state(delivery.state)
+            .with_attempts(delivery.attempts);
+
+        if let Some(response_status) = delivery.response_status {
+            response.with_response_status(response_status);
+        }
+
+        if let Some(error) = &delivery.error {
+            response.with_error(error);
+        }
+
+        if let Some(next_attempt_at) = delivery.next_attempt_at {
+            response.with_next_attempt_at(next_attempt_at);
+        }
+
+        if let Some(created_at) = delivery.created_at {
+            response.with_created_at(created_at);
+        }
+
+        if let Some(updated_at) = delivery.updated_at {
+            response.with_updated_at(updated_at);
+        }
+
+        response.build().ok()
+    }
+
+    // scan enqueues the extrinsics of chain_id changed since the persisted cursor and
+    // moves it past them, enqueueing is idempotent so a restart in between only
+    // repeats work, a chain without cursor starts from its latest revision
+    pub async fn scan(&self, chain_id: &str) -> Result<(), DbErr> {
+        let cursor = entities::webhook_cursor::Entity::find_by_id(chain_id)
+            .one(self.db.as_ref())
+            .await?;
+
+        let since = match cursor {
+            Some(cursor) => cursor.revision,
+            None => entities::extrinsic::Entity::find()
+                .select_only()
+                .column_as(entities::extrinsic::Column::Revision.max(), "revision")
+                .filter(entities::extrinsic::Column::ChainId.eq(chain_id))
+                .into_tuple::<Option<i64>>()
+                .one(self.db.as_ref())
+                .await?
+                .flatten()
+                .unwrap_or_default(),
+        };
+
+        let extrinsics = entities::extrinsic::Entity::find()
+            .filter(entities::extrinsic::Column::ChainId.eq(chain_id))
+            .filter(entities::extrinsic::Column::Revision.gt(since))
+            .order_by_asc(entities::extrinsic::Column::Revision)
+            .all(self.db.as_ref())
+            .await?;
+
+        // the cursor only moves once every delivery is recorded
+        for extrinsic in &extrinsics {
+            self.enqueue(extrinsic).await?;
+        }
+
+        let revision = extrinsics
+            .last()
+            .map_or(since, |extrinsic| extrinsic.revision);
+
+        let cursor = entities::webhook_cursor::ActiveModel {
+            chain_id: Set(chain_id.to_owned()),
+            revision: Set(revision),
+            updated_at: Set(Some(OffsetDateTime::now_utc())),
+        };
+
+        entities::webhook_cursor::Entity::insert(cursor)
+            .on_conflict(
+                OnConflict::column(entities::webhook_cursor::Column::ChainId)
+                    .update_columns([
+                        entities::webhook_cursor::Column::Revision,
+                        entities::webhook_cursor::Column::UpdatedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn enqueue(&self, extrinsic: &entities::extrinsic::Model) -> Result<(), DbErr> {
+        match extrinsic.state {
+            BlockState::Finalized | BlockState::Dropped => (),
+            _ => return Ok(()),
+        }
+
+        let webhooks = entities::webhook::Entity::find()
+            .filter(entities::webhook::Column::ChainId.eq(&extrinsic.chain_id))
+            .filter(entities::webhook::Column::Enabled.eq(true))
+            .all(self.db.as_ref())
+            .await?
+            .into_iter()
+            .filter(|webhook| Self::matches(webhook, extrinsic))
+            .collect::<Vec<_>>();
+
+        if webhooks.is_empty() {
+            return Ok(());
+        }
+
+        let data = match self.extrinsic_manager.dump(extrinsic, false).await {
+            Some(data) => data,
+            None => {
+                log::warn!("failed to describe extrinsic {} for webhooks", extrinsic.id);
+                return Ok(());
+            }
+        };
+
+        let now = OffsetDateTime::now_utc();
+
+        for webhook in webhooks {
+            let id = self.id.next_id();
+
+            let payload = WebhookPayload::builder()
+                .with_id(id)
+                .with_event(extrinsic.state)
+                .with_data(data.clone())
+                .build()
+                .unwrap();
+
+            let payload = match serde_json::to_string(&payload) {
+                Ok(payload) => payload,
+                Err(_) => continue,
+            };
+
+            let delivery = entities::webhook_delivery::ActiveModel {
+                id: Set(id),
+                webhook_id: Set(webhook.id),
+                extrinsic_id: Set(extrinsic.id),
+                event: Set(extrinsic.state),
+                payload: Set(payload),
+                state: Set(DeliveryState::Pending),
+                attempts: Set(0),
+                response_status: Set(None),
+                error: Set(None),
+                next_attempt_at: Set(Some(now)),
+                created_at: Set(Some(now)),
+                updated_at: Set(Some(now)),
+            };
+
+            // a delivery is recorded once per webhook, extrinsic and event
+            entities::webhook_delivery::Entity::insert(delivery)
+                .on_conflict(
+                    OnConflict::columns([
+                        entities::webhook_delivery::Column::WebhookId,
+                        entities::webhook_delivery::Column::ExtrinsicId,
+                        entities::webhook_delivery::Column::Event,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .exec_without_returning(self.db.as_ref())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn deliver(&self, setting: &WebhookSetting) {
+        let now = OffsetDateTime::now_utc();
+
+        let deliveries = match entities::webhook_delivery::Entity::find()
+            .filter(entities::webhook_delivery::Column::State.eq(DeliveryState::Pending))
+            .filter(entities::webhook_delivery::Column::NextAttemptAt.lte(now))
+            .order_by_asc(entities::webhook_delivery::Column::NextAttemptAt)
+            .limit(setting.batch())
+            .all(self.db.as_ref())
+            .await
+        {
+            Ok(deliveries) => deliveries,
+            Err(err) => {
+                log::warn!("failed to find pending deliveries: {}", err);
+                return;
+            }
+        };
+
+        for delivery in deliveries {
+            // claim the delivery so that concurrent workers do not send it twice
+            let lease = now + Duration::from_secs(setting.timeout() * 2);
+            let claimed = entities::webhook_delivery::Entity::update_many()
+                .col_expr(
+                    entities::webhook_delivery::Column::NextAttemptAt,
+                    Expr::value(lease),
+                )
+                .filter(entities::webhook_delivery::Column::Id.eq(delivery.id))
+                .filter(entities::webhook_delivery::Column::State.eq(DeliveryState::Pending))
+                .filter(
+                    entities::webhook_delivery::Column::NextAttemptAt.eq(delivery.next_attempt_at),
+                )
+                .exec(self.db.as_ref())
+                .await
+                .map(|result| result.rows_affected == 1)
+                .unwrap_or_default();
+
+            if !claimed {
+                continue;
+            }
+
+            let webhook = match self.get(delivery.webhook_id).await {
+                Some(webhook) => webhook,
+                None => continue,
+            };
+
+            // the url is resolved again on every attempt, it may have been pointed at an
+            // internal address since it was registered
+            let (response_status, error) = match Self::client(&webhook, setting).await {
+                Some(client) => Self::send(&client, &webhook, &delivery).await,
+                None => (
+                    None,
+                    Some("webhook url does not resolve to a public address".to_owned()),
+                ),
+            };
+
+            let attempts = delivery.attempts + 1;
+            let succeeded = error.is_none();
+
+            let mut delivery: entities::webhook_delivery::ActiveModel = delivery.into();
+            delivery.attempts = Set(attempts);
+            delivery.response_status = Set(response_status);
+            delivery.error = Set(error);
+            delivery.updated_at = Set(Some(OffsetDateTime::now_utc()));
+
+            if succeeded {
+                delivery.state = Set(DeliveryState::Delivered);
+                delivery.next_attempt_at = Set(None);
+            } else if attempts >= setting.max_attempts() {
+                delivery.state = Set(DeliveryState::Failed);
+                delivery.next_attempt_at = Set(None);
+            } else {
+                let backoff = setting
+                    .backoff()
+                    .saturating_mul(1 << (attempts - 1).min(32))
+                    .min(setting.max_backoff());
+
+                delivery.next_attempt_at = Set(Some(
+                    OffsetDateTime::now_utc() + Duration::from_secs(backoff),
+                ));
+            }
+
+            if let Err(err) = delivery.update(self.db.as_ref()).await {
+                log::warn!("failed to update delivery: {}", err);
+            }
+        }
+    }
+
+    fn matches(webhook: &entities::webhook::Model, extrinsic: &entities::extrinsic::Model) -> bool {
+        if let Some(asset_id) = &webhook.asset_id {
+            if asset_id != &extrinsic.asset_id {
+                return false;
+            }
+        }
+
+        if let Some(address) = &webhook.address {
+            if address != &extrinsic.from_address && address != &extrinsic.to_address {
+                return false;
+            }
+        }
+
+        if let Some(operation) = webhook.operation {
+            if operation != extrinsic.operation {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    // client pins the connection to the addresses the url was checked against, so
+    // the host cannot be rebound to an internal address in between
+    async fn client(
+        webhook: &entities::webhook::Model,
+        setting: &WebhookSetting,
+    ) -> Option<reqwest::Client> {
+        let (host, addrs) = resolve_public(&webhook.url).await?;
+
+        reqwest::Client::builder()
+            .timeout(Duration::from_secs(setting.timeout()))
+            .redirect(reqwest::redirect::Policy::none())
+            .resolve_to_addrs(&host, &addrs)
+            .build()
+            .ok()
+    }
+
+    async fn send(
+        client: &reqwest::Client,
+        webhook: &entities::webhook::Model,
+        delivery: &entities::webhook_delivery::Model,
+    ) -> (Option<i32>, Option<String>) {
+        let timestamp = OffsetDateTime::now_utc().unix_timestamp().to_string();
+
+        let mut mac = match Hmac::<Sha256>::new_from_slice(webhook.secret.as_bytes()) {
+            Ok(mac) => mac,
+            Err(err) => return (None, Some(err.to_string())),
+        };
+        mac.update(timestamp.as_bytes());
+        mac.update(b".");
+        mac.update(delivery.payload.as_bytes());
+        let signature = hex::encode(mac.finalize().into_bytes());
+
+        let response = client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-EOS420-Delivery", delivery.id.to_string())
+            .header("X-EOS420-Timestamp", &timestamp)
+            .header("X-EOS420-Signature", format!("sha256={}", signature))
+            .body(delivery.payload.clone())
+            .send()
+            .await;
+
+        match response {
+            Ok(response) => {
+                let status = response.status();
+                if status.is_success() {
+                    (Some(status.as_u16() as i32), None)
+                } else {
+                    (
+                        Some(status.as_u16() as i32),
+                        Some(format!("unexpected status {}", status)),
+                    )
+                }
+            }
+            Err(err) => (None, Some(err.to_string())),
+        }
+    }
+}
@@ -13,3 +13,23 @@ pub fn calculate_amount(amount: &Uint256, decimals: i32) -> f64 {
 
     amount / base
 }
+
+// format_amount renders amount with decimals as an exact decimal string
+pub fn format_amount(amount: &Uint256, decimals: i32) -> String {
+    let digits = amount.to_string();
+    let decimals = decimals.max(0) as usize;
+
+    if decimals == 0 {
+        return digits;
+    }
+
+    let digits = format!("{:0>width$}", digits, width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        integer.to_owned()
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
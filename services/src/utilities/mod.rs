@@ -1,3 +1,5 @@
 mod currency;
+mod network;
 
-pub use currency::calculate_amount;
+pub use currency::{calculate_amount, format_amount};
+pub use network::{resolve_public, routable};
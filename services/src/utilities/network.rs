@@ -0,0 +1,115 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use reqwest::Url;
+
+fn routable_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // this network, shared address space, protocol assignments, benchmarking
+        // and the reserved 240.0.0.0/4 block
+        || a == 0
+        || (a == 100 && (b & 0xc0) == 64)
+        || (a == 192 && b == 0 && c == 0)
+        || (a == 198 && (b & 0xfe) == 18)
+        || a >= 240)
+}
+
+fn routable_v6(ip: Ipv6Addr) -> bool {
+    if let Some(ip) = ip.to_ipv4_mapped() {
+        return routable_v4(ip);
+    }
+
+    let [a, b, ..] = ip.segments();
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        || ip.is_unique_local()
+        || ip.is_unicast_link_local()
+        // documentation
+        || (a == 0x2001 && b == 0x0db8))
+}
+
+// routable tells whether ip is a public address, loopback, link-local and
+// private ranges are refused
+pub fn routable(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => routable_v4(ip),
+        IpAddr::V6(ip) => routable_v6(ip),
+    }
+}
+
+// resolve_public looks up the host of an http(s) url and returns it with its
+// addresses, None when any of them is not publicly routable
+pub async fn resolve_public(url: &str) -> Option<(String, Vec<SocketAddr>)> {
+    let url = Url::parse(url).ok()?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+
+    let host = url.host_str()?.to_owned();
+    let port = url.port_or_known_default()?;
+
+    // ip literals are bracketed in urls
+    let lookup = host.trim_start_matches('[').trim_end_matches(']');
+    let addrs = tokio::net::lookup_host((lookup, port))
+        .await
+        .ok()?
+        .collect::<Vec<_>>();
+
+    if addrs.is_empty() || !addrs.iter().all(|addr| routable(addr.ip())) {
+        return None;
+    }
+
+    Some((host, addrs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_internal_addresses() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fe80::1",
+            "fd00::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!routable(ip.parse().unwrap()), "{} is internal", ip);
+        }
+    }
+
+    #[test]
+    fn accepts_public_addresses() {
+        for ip in ["1.1.1.1", "93.184.216.34", "2606:4700:4700::1111"] {
+            assert!(routable(ip.parse().unwrap()), "{} is public", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn refuses_internal_urls() {
+        assert!(resolve_public("http://127.0.0.1:8080/hook").await.is_none());
+        assert!(resolve_public("http://[::1]/hook").await.is_none());
+        assert!(resolve_public("http://169.254.169.254/latest")
+            .await
+            .is_none());
+        assert!(resolve_public("ftp://1.1.1.1/hook").await.is_none());
+        assert!(resolve_public("https://1.1.1.1/hook").await.is_some());
+    }
+}
@@ -0,0 +1,344 @@
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ff::{BigInteger as _, PrimeField};
+use ark_groth16::Groth16;
+use sha2::{Digest as _, Sha256};
+
+use crate::{
+    entities::{self, DropReason},
+    primitives::ordinal::{self, Ordinal},
+};
+
+// field parses a big-endian element and rejects values outside of the field
+fn field<F: PrimeField>(bytes: &[u8]) -> Option<F> {
+    if bytes.len() > 32 {
+        return None;
+    }
+
+    let element = F::from_be_bytes_mod_order(bytes);
+
+    let mut canonical = element.into_bigint().to_bytes_be();
+    let offset = canonical.len().checked_sub(bytes.len())?;
+    if canonical.drain(..offset).any(|byte| byte != 0) || canonical != bytes {
+        return None;
+    }
+
+    Some(element)
+}
+
+fn g1(point: &[Vec<u8>; 2]) -> Option<G1Affine> {
+    let point = G1Affine::new_unchecked(field::<Fq>(&point[0])?, field::<Fq>(&point[1])?);
+
+    (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve()).then_some(point)
+}
+
+fn g2(point: &[[Vec<u8>; 2]; 2]) -> Option<G2Affine> {
+    let [x, y] = point;
+    let x = Fq2::new(field::<Fq>(&x[1])?, field::<Fq>(&x[0])?);
+    let y = Fq2::new(field::<Fq>(&y[1])?, field::<Fq>(&y[0])?);
+
+    let point = G2Affine::new_unchecked(x, y);
+
+    (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve()).then_some(point)
+}
+
+fn verifying_key(key: &ordinal::VerifyingKey) -> Option<ark_groth16::VerifyingKey<Bn254>> {
+    Some(ark_groth16::VerifyingKey {
+        alpha_g1: g1(&key.alpha)?,
+        beta_g2: g2(&key.beta)?,
+        gamma_g2: g2(&key.gamma)?,
+        delta_g2: g2(&key.delta)?,
+        gamma_abc_g1: key.ic.iter().map(g1).collect::<Option<Vec<_>>>()?,
+    })
+}
+
+pub fn validate_key(key: &ordinal::VerifyingKey) -> bool {
+    verifying_key(key).is_some_and(|key| key.gamma_abc_g1.len() == 3)
+}
+
+pub fn verify(
+    key: &ordinal::VerifyingKey,
+    a: &ordinal::A,
+    proof: &ordinal::Proof,
+) -> Result<(), DropReason> {
+    let key = verifying_key(key).ok_or(DropReason::ProofInvalid)?;
+
+    let proof = ark_groth16::Proof::<Bn254> {
+        a: g1(&proof.0).ok_or(DropReason::ProofInvalid)?,
+        b: g2(&proof.1).ok_or(DropReason::ProofInvalid)?,
+        c: g1(&proof.2).ok_or(DropReason::ProofInvalid)?,
+    };
+
+    let inputs = [field::<Fr>(&a.0), field::<Fr>(&a.1)]
+        .into_iter()
+        .collect::<Option<Vec<_>>>()
+        .ok_or(DropReason::ProofInvalid)?;
+
+    if key.gamma_abc_g1.len() != inputs.len() + 1 {
+        return Err(DropReason::ProofInvalid);
+    }
+
+    let key = ark_groth16::prepare_verifying_key(&key);
+
+    match Groth16::<Bn254>::verify_proof(&key, &proof, &inputs) {
+        Ok(true) => Ok(()),
+        _ => Err(DropReason::ProofInvalid),
+    }
+}
+
+// statement derives the public inputs from the operation and its sender, the two
+// halves of the digest fit the scalar field so a proof only holds for what it was
+// generated for and cannot be replayed with other operands or by someone else
+pub fn statement(
+    contract: &entities::contract::Model,
+    ordinal: &Ordinal,
+    sender: &str,
+) -> ordinal::A {
+    let operands = ordinal
+        .operands()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|operand| {
+            (
+                operand.id.as_deref(),
+                operand.amount.as_deref(),
+                operand.to.as_deref().map(str::to_lowercase),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let preimage = serde_json::to_vec(&(
+        &contract.chain_id,
+        contract.address.to_lowercase(),
+        sender.to_lowercase(),
+        &ordinal.tick,
+        ordinal.operation,
+        operands,
+    ))
+    .unwrap_or_default();
+
+    let digest = Sha256::digest(preimage);
+
+    ordinal::A(digest[..16].to_vec(), digest[16..].to_vec())
+}
+
+// verify_ordinal checks the proof an ordinal carries against the key registered
+// for its contract, contracts with a key require every operation to carry a proof
+// whose public inputs are the statement of the operation
+pub fn verify_ordinal(
+    contract: &entities::contract::Model,
+    ordinal: &Ordinal,
+    sender: &str,
+) -> Result<(), DropReason> {
+    let key = match &contract.verifying_key {
+        Some(key) => serde_json::from_str::<ordinal::VerifyingKey>(key)
+            .map_err(|_| DropReason::ProofInvalid)?,
+        None if ordinal.a.is_none() && ordinal.proof.is_none() => return Ok(()),
+        None => return Err(DropReason::OperationUnsupported),
+    };
+
+    let statement = statement(contract, ordinal, sender);
+
+    match (&ordinal.a, &ordinal.proof) {
+        (Some(a), Some(proof)) if a == &statement => verify(&key, &statement, proof),
+        _ => Err(DropReason::ProofInvalid),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ec::AffineRepr as _;
+    use ark_ff::Field as _;
+    use ark_relations::{
+        lc,
+        r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError},
+    };
+    use rand::{rngs::StdRng, SeedableRng as _};
+
+    use super::*;
+    use crate::entities::{ContractState, ContractType, ExtrinsicOperation};
+
+    const SENDER: &str = "0x1111111111111111111111111111111111111111";
+
+    // Product proves knowledge of w with w * x = y for the public inputs x and y
+    struct Product {
+        x: Fr,
+        y: Fr,
+    }
+
+    impl ConstraintSynthesizer<Fr> for Product {
+        fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+            let x = cs.new_input_variable(|| Ok(self.x))?;
+            let y = cs.new_input_variable(|| Ok(self.y))?;
+            let w = cs.new_witness_variable(|| {
+                self.x
+                    .inverse()
+                    .map(|inverse| self.y * inverse)
+                    .ok_or(SynthesisError::DivisionByZero)
+            })?;
+
+            cs.enforce_constraint(lc!() + w, lc!() + x, lc!() + y)
+        }
+    }
+
+    fn bytes<F: PrimeField>(element: F) -> Vec<u8> {
+        element.into_bigint().to_bytes_be()
+    }
+
+    fn encode_g1(point: G1Affine) -> [Vec<u8>; 2] {
+        let (x, y) = point.xy().unwrap();
+
+        [bytes(*x), bytes(*y)]
+    }
+
+    fn encode_g2(point: G2Affine) -> [[Vec<u8>; 2]; 2] {
+        let (x, y) = point.xy().unwrap();
+
+        [[bytes(x.c1), bytes(x.c0)], [bytes(y.c1), bytes(y.c0)]]
+    }
+
+    fn contract(verifying_key: Option<String>) -> entities::contract::Model {
+        entities::contract::Model {
+            id: 1,
+            class_id: 1,
+            chain_id: "1".to_owned(),
+            asset_id: "zk".to_owned(),
+            address: "0x2222222222222222222222222222222222222222".to_owned(),
+            owner: None,
+            protocol: ContractType::Eos20,
+            decimals: Some(18),
+            identifier: None,
+            max_supply: Some("1000".to_owned()),
+            mint_limit: None,
+            minted: "0x0".to_owned(),
+            not_before: None,
+            tx_hash: None,
+            state: ContractState::Deployed,
+            deployed_at: None,
+            expires_at: None,
+            verifying_key,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn transfer(amount: &str) -> Ordinal {
+        Ordinal::builder()
+            .with_protocol(ContractType::Eos20)
+            .with_tick("zk")
+            .with_operation(ExtrinsicOperation::Transfer)
+            .with_operand(ordinal::SingleOrBatch::Single(ordinal::Operand {
+                id: None,
+                amount: Some(amount.to_owned()),
+                to: Some("0x3333333333333333333333333333333333333333".to_owned()),
+            }))
+            .build()
+            .unwrap()
+    }
+
+    // prove returns the contract holding the verifying key and the transfer carrying
+    // a proof for its statement
+    fn prove() -> (entities::contract::Model, Ordinal) {
+        let mut rng = StdRng::seed_from_u64(420);
+
+        let mut ordinal = transfer("10");
+        let mut contract = contract(None);
+
+        let a = statement(&contract, &ordinal, SENDER);
+        let (x, y) = (
+            Fr::from_be_bytes_mod_order(&a.0),
+            Fr::from_be_bytes_mod_order(&a.1),
+        );
+
+        let parameters =
+            Groth16::<Bn254>::generate_random_parameters_with_reduction(Product { x, y }, &mut rng)
+                .unwrap();
+        let proof = Groth16::<Bn254>::create_random_proof_with_reduction(
+            Product { x, y },
+            &parameters,
+            &mut rng,
+        )
+        .unwrap();
+
+        let key = parameters.vk;
+        let key = ordinal::VerifyingKey {
+            alpha: encode_g1(key.alpha_g1),
+            beta: encode_g2(key.beta_g2),
+            gamma: encode_g2(key.gamma_g2),
+            delta: encode_g2(key.delta_g2),
+            ic: key.gamma_abc_g1.into_iter().map(encode_g1).collect(),
+        };
+        assert!(validate_key(&key));
+
+        contract.verifying_key = Some(serde_json::to_string(&key).unwrap());
+
+        ordinal.a = Some(a);
+        ordinal.proof = Some(ordinal::Proof(
+            encode_g1(proof.a),
+            encode_g2(proof.b),
+            encode_g1(proof.c),
+        ));
+
+        (contract, ordinal)
+    }
+
+    #[test]
+    fn accepts_proof_bound_to_operation() {
+        let (contract, ordinal) = prove();
+
+        assert_eq!(verify_ordinal(&contract, &ordinal, SENDER), Ok(()));
+    }
+
+    #[test]
+    fn refuses_replayed_proof() {
+        let (contract, ordinal) = prove();
+
+        // the same proof sent by someone else
+        assert_eq!(
+            verify_ordinal(
+                &contract,
+                &ordinal,
+                "0x4444444444444444444444444444444444444444"
+            ),
+            Err(DropReason::ProofInvalid)
+        );
+
+        // the same proof attached to other operands
+        let mut replayed = transfer("1000");
+        replayed.a = ordinal.a.clone();
+        replayed.proof = ordinal.proof.clone();
+        assert_eq!(
+            verify_ordinal(&contract, &replayed, SENDER),
+            Err(DropReason::ProofInvalid)
+        );
+
+        // public inputs recomputed for the other operands do not match the proof
+        replayed.a = Some(statement(&contract, &replayed, SENDER));
+        assert_eq!(
+            verify_ordinal(&contract, &replayed, SENDER),
+            Err(DropReason::ProofInvalid)
+        );
+    }
+
+    #[test]
+    fn refuses_missing_proof_and_broken_key() {
+        let (mut contract, mut ordinal) = prove();
+
+        ordinal.proof = None;
+        assert_eq!(
+            verify_ordinal(&contract, &ordinal, SENDER),
+            Err(DropReason::ProofInvalid)
+        );
+
+        let (_, ordinal) = prove();
+        contract.verifying_key = Some("{}".to_owned());
+        assert_eq!(
+            verify_ordinal(&contract, &ordinal, SENDER),
+            Err(DropReason::ProofInvalid)
+        );
+
+        assert_eq!(
+            verify_ordinal(&self::contract(None), &ordinal, SENDER),
+            Err(DropReason::OperationUnsupported)
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use sea_orm::{
+    ColumnTrait as _, DatabaseConnection, DbErr, EntityTrait as _, QueryFilter as _,
+    QueryOrder as _, QuerySelect as _,
+};
+
+use crate::{
+    entities::{self, BlockState},
+    event::{Event, EventService},
+};
+
+// Cursor holds the latest revisions published for a chain, the indexer bumps the
+// revision of a row whenever its state changes and commits them in order, so
+// nothing committed is skipped regardless of clocks
+struct Cursor {
+    block: i64,
+    extrinsic: i64,
+}
+
+impl Cursor {
+    // start resumes from what is already stored, history is not replayed
+    async fn start(db: &DatabaseConnection, chain_id: &str) -> Result<Self, DbErr> {
+        let block = entities::block::Entity::find()
+            .select_only()
+            .column_as(entities::block::Column::Revision.max(), "revision")
+            .filter(entities::block::Column::ChainId.eq(chain_id))
+            .into_tuple::<Option<i64>>()
+            .one(db)
+            .await?
+            .flatten()
+            .unwrap_or_default();
+
+        let extrinsic = entities::extrinsic::Entity::find()
+            .select_only()
+            .column_as(entities::extrinsic::Column::Revision.max(), "revision")
+            .filter(entities::extrinsic::Column::ChainId.eq(chain_id))
+            .into_tuple::<Option<i64>>()
+            .one(db)
+            .await?
+            .flatten()
+            .unwrap_or_default();
+
+        Ok(Self { block, extrinsic })
+    }
+}
+
+pub async fn watch(
+    db: Arc<DatabaseConnection>,
+    event: Arc<EventService>,
+    chains: Vec<String>,
+    interval: Duration,
+) {
+    let mut cursors = HashMap::<String, Cursor>::new();
+
+    loop {
+        for chain_id in &chains {
+            let cursor = match cursors.get_mut(chain_id) {
+                Some(cursor) => cursor,
+                None => {
+                    match Cursor::start(db.as_ref(), chain_id).await {
+                        Ok(cursor) => {
+                            cursors.insert(chain_id.clone(), cursor);
+                        }
+                        Err(err) => log::warn!("failed to start watching {}: {}", chain_id, err),
+                    }
+
+                    continue;
+                }
+            };
+
+            match entities::block::Entity::find()
+                .filter(entities::block::Column::ChainId.eq(chain_id))
+                .filter(entities::block::Column::Revision.gt(cursor.block))
+                .order_by_asc(entities::block::Column::Revision)
+                .all(db.as_ref())
+                .await
+            {
+                Ok(rows) => {
+                    for block in rows {
+                        cursor.block = cursor.block.max(block.revision);
+
+                        // the revert stamps the block and the extrinsics it dropped with the
+                        // same revision, extrinsics dropped before keep theirs
+                        if block.state == BlockState::Dropped {
+                            match entities::extrinsic::Entity::find()
+                                .filter(entities::extrinsic::Column::ChainId.eq(&block.chain_id))
+                                .filter(
+                                    entities::extrinsic::Column::BlockHash.eq(&block.block_hash),
+                                )
+                                .filter(entities::extrinsic::Column::State.eq(BlockState::Dropped))
+                                .filter(entities::extrinsic::Column::Revision.eq(block.revision))
+                                .all(db.as_ref())
+                                .await
+                            {
+                                Ok(reverted) => {
+                                    event.publish(Event::Reorg(block.clone(), reverted))
+                                }
+                                Err(err) => {
+                                    log::warn!("failed to load reverted extrinsics: {}", err)
+                                }
+                            }
+                        }
+
+                        event.publish(Event::Block(block));
+                    }
+                }
+                Err(err) => log::warn!("failed to watch block changes: {}", err),
+            }
+
+            match entities::extrinsic::Entity::find()
+                .filter(entities::extrinsic::Column::ChainId.eq(chain_id))
+                .filter(entities::extrinsic::Column::Revision.gt(cursor.extrinsic))
+                .order_by_asc(entities::extrinsic::Column::Revision)
+                .all(db.as_ref())
+                .await
+            {
+                Ok(rows) => {
+                    for extrinsic in rows {
+                        cursor.extrinsic = cursor.extrinsic.max(extrinsic.revision);
+
+                        event.publish(Event::Extrinsic(extrinsic));
+                    }
+                }
+                Err(err) => log::warn!("failed to watch extrinsic changes: {}", err),
+            }
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}